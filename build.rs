@@ -0,0 +1,303 @@
+//! Build script: compiles the JSON width datasets into compact static tables.
+//!
+//! Each dataset in `src/assets/` is a JSON object whose keys are graphemes.
+//! Keys are split into two dense forms, written to `$OUT_DIR`:
+//!
+//! - single characters, merged into sorted inclusive code point ranges
+//!   (the CJK dataset alone collapses from ~93k entries to a handful of ranges)
+//! - multi-codepoint sequences, concatenated in `str` order into one binary
+//!   blob, with a table of end offsets for binary search
+//!
+//! Nothing is parsed or allocated at runtime (required for `no_std`).
+//!
+//! Datasets can be left out with cargo features (`hangul`, `kana`,
+//! `emoji-zwj`, `cjk-ext`). ZWJ sequences are then dropped, and the other
+//! tables fall back to the fixed Unicode blocks in `FALLBACKS`.
+//!
+//! Single-character keys of all tables are also merged into `WIDE_CHARS`,
+//! used by the `const fn` API.
+//!
+//! `east_asian_ambiguous.json` holds code point ranges instead of graphemes
+//! and is written to its own file, `ambiguous.rs` (see
+//! `scripts/gen_east_asian_ambiguous.py`). Likewise, `grapheme_break.json`
+//! holds the UAX #29 classes used by the `const fn` segmenter and is written
+//! to `grapheme_break.rs` (see `scripts/gen_grapheme_break.py`).
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Generated table name and the dataset files it is built from.
+const TABLES: &[(&str, &[&str])] = &[
+    ("CJK_UNIFIED", &["cjk_unified.json"]),
+    ("EMOJI", &["emoji_base.json", "emoji_zwj.json"]),
+    ("FULLWIDTH_PUNCTUATIONS", &["fullwidth_punctuations.json"]),
+    ("FULLWIDTH_VARIANTS", &["fullwidth_variants.json"]),
+    ("JAPANESE_KANA", &["japanese_kana.json"]),
+    ("KOREAN_SYLLABLES", &["korean_syllables.json"]),
+];
+
+/// CJK Unified Ideographs and Compatibility Ideographs, kept without `cjk-ext`.
+const CJK_CORE: &[(u32, u32)] = &[(0x4E00, 0x9FFF), (0xF900, 0xFAFF)];
+
+/// Inclusive code point ranges.
+type Ranges = &'static [(u32, u32)];
+
+/// Table name, the feature that includes its dataset, and the blocks used instead.
+const FALLBACKS: &[(&str, &str, Ranges)] = &[
+    // Hangul Syllables
+    ("KOREAN_SYLLABLES", "HANGUL", &[(0xAC00, 0xD7A3)]),
+    // Hiragana, Katakana, Katakana Phonetic Extensions, and Kana Supplement
+    // through Small Kana Extension
+    (
+        "JAPANESE_KANA",
+        "KANA",
+        &[(0x3040, 0x30FF), (0x31F0, 0x31FF), (0x1B000, 0x1B16F)],
+    ),
+    // Extension A, and the Supplementary and Tertiary Ideographic Planes
+    // (Extension B onward)
+    (
+        "CJK_UNIFIED",
+        "CJK_EXT",
+        &[(0x3400, 0x4DBF), (0x20000, 0x2FFFD), (0x30000, 0x3FFFD)],
+    ),
+];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut out = String::from("// @generated by build.rs from src/assets/*.json\n");
+    let mut wide = Vec::new();
+
+    for (name, files) in TABLES {
+        let mut keys = Vec::new();
+
+        for file in *files {
+            let path = format!("src/assets/{file}");
+            println!("cargo:rerun-if-changed={path}");
+
+            let json = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+            let map: serde_json::Value =
+                serde_json::from_str(&json).unwrap_or_else(|e| panic!("Invalid {file}: {e}"));
+            let object = map
+                .as_object()
+                .unwrap_or_else(|| panic!("{file}: expected a JSON object"));
+
+            keys.extend(object.keys().filter(|k| included(name, k)).cloned());
+        }
+
+        // `str` ordering, so that sequences can be binary searched byte-wise.
+        keys.sort_unstable();
+        keys.dedup();
+
+        let (chars, sequences): (Vec<_>, Vec<_>) =
+            keys.into_iter().partition(|k| k.chars().count() == 1);
+        let chars: Vec<u32> = chars
+            .iter()
+            .filter_map(|k| k.chars().next())
+            .map(u32::from)
+            .chain(fallback(name).iter().flat_map(|&(lo, hi)| lo..=hi))
+            .collect();
+
+        wide.extend_from_slice(&chars);
+
+        let blob = format!("{}.bin", name.to_lowercase());
+        fs::write(Path::new(&out_dir).join(&blob), sequences.concat()).unwrap();
+
+        writeln!(out, "\npub(crate) static {name}: Table = Table {{").unwrap();
+        writeln!(out, "    ranges: &[").unwrap();
+        for (start, end) in to_ranges(chars) {
+            writeln!(out, "        (0x{start:04X}, 0x{end:04X}),").unwrap();
+        }
+        writeln!(out, "    ],").unwrap();
+        writeln!(
+            out,
+            "    sequences: include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{blob}\")),"
+        )
+        .unwrap();
+        writeln!(out, "    ends: &[").unwrap();
+        let mut end = 0;
+        for seq in &sequences {
+            end += seq.len();
+            writeln!(out, "        {end},").unwrap();
+        }
+        writeln!(out, "    ],").unwrap();
+        writeln!(out, "}};").unwrap();
+    }
+
+    writeln!(out, "\npub(crate) static WIDE_CHARS: &[(u32, u32)] = &[").unwrap();
+    for (start, end) in to_ranges(wide) {
+        writeln!(out, "    (0x{start:04X}, 0x{end:04X}),").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    fs::write(Path::new(&out_dir).join("tables.rs"), out).unwrap();
+    write_ambiguous(&out_dir);
+    write_grapheme_break(&out_dir);
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+/// Writes the East Asian Ambiguous ranges, noting the Unicode version they match.
+fn write_ambiguous(out_dir: &str) {
+    let path = "src/assets/east_asian_ambiguous.json";
+    println!("cargo:rerun-if-changed={path}");
+
+    let json = fs::read_to_string(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+    let data: serde_json::Value =
+        serde_json::from_str(&json).unwrap_or_else(|e| panic!("Invalid {path}: {e}"));
+    let version = data["unicode_version"]
+        .as_str()
+        .unwrap_or_else(|| panic!("{path}: missing unicode_version"));
+    let ranges = data["ranges"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{path}: missing ranges"));
+
+    let mut out = format!("// @generated by build.rs from {path}\n");
+    writeln!(
+        out,
+        "\n/// `East_Asian_Width=A` ranges of Unicode {version}."
+    )
+    .unwrap();
+    writeln!(out, "pub(crate) static AMBIGUOUS: &[(u32, u32)] = &[").unwrap();
+    for range in ranges {
+        let bound = |i: usize| {
+            range[i]
+                .as_str()
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| panic!("{path}: invalid range {range}"))
+        };
+        writeln!(out, "    (0x{:04X}, 0x{:04X}),", bound(0), bound(1)).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    fs::write(Path::new(out_dir).join("ambiguous.rs"), out).unwrap();
+}
+
+/// Writes the grapheme cluster break classes, noting the Unicode version they match.
+fn write_grapheme_break(out_dir: &str) {
+    let path = "src/assets/grapheme_break.json";
+    println!("cargo:rerun-if-changed={path}");
+
+    let json = fs::read_to_string(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+    let data: serde_json::Value =
+        serde_json::from_str(&json).unwrap_or_else(|e| panic!("Invalid {path}: {e}"));
+    let version = data["unicode_version"]
+        .as_str()
+        .unwrap_or_else(|| panic!("{path}: missing unicode_version"));
+    let rows = |key: &str| {
+        data[key]
+            .as_array()
+            .unwrap_or_else(|| panic!("{path}: missing {key}"))
+    };
+    let bound = |row: &serde_json::Value, i: usize| {
+        row[i]
+            .as_str()
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .unwrap_or_else(|| panic!("{path}: invalid range {row}"))
+    };
+
+    let mut out = format!("// @generated by build.rs from {path}\n");
+    writeln!(
+        out,
+        "\n/// Grapheme cluster break classes of Unicode {version}."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "pub(crate) static GRAPHEME_BREAK: &[(u32, u32, GraphemeBreak)] = &["
+    )
+    .unwrap();
+    for row in rows("classes") {
+        let class = match row[2].as_str() {
+            Some("CR") => "Cr",
+            Some("LF") => "Lf",
+            Some("Control") => "Control",
+            Some("Extend") => "Extend",
+            Some("ZWJ") => "Zwj",
+            Some("Regional_Indicator") => "RegionalIndicator",
+            Some("Prepend") => "Prepend",
+            Some("SpacingMark") => "SpacingMark",
+            Some("L") => "L",
+            Some("V") => "V",
+            Some("T") => "T",
+            Some("LV") => "Lv",
+            Some("LVT") => "Lvt",
+            Some("Extended_Pictographic") => "ExtendedPictographic",
+            Some("InCB_Consonant") => "InCbConsonant",
+            _ => panic!("{path}: unknown class in {row}"),
+        };
+        writeln!(
+            out,
+            "    (0x{:04X}, 0x{:04X}, GraphemeBreak::{class}),",
+            bound(row, 0),
+            bound(row, 1)
+        )
+        .unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    for (name, key) in [
+        ("INCB_EXTEND", "incb_extend"),
+        ("INCB_LINKER", "incb_linker"),
+    ] {
+        writeln!(out, "\npub(crate) static {name}: &[(u32, u32)] = &[").unwrap();
+        for row in rows(key) {
+            writeln!(
+                out,
+                "    (0x{:04X}, 0x{:04X}),",
+                bound(row, 0),
+                bound(row, 1)
+            )
+            .unwrap();
+        }
+        writeln!(out, "];").unwrap();
+    }
+
+    fs::write(Path::new(out_dir).join("grapheme_break.rs"), out).unwrap();
+}
+
+/// Returns whether `key` of table `name` is kept under the enabled features.
+fn included(name: &str, key: &str) -> bool {
+    match name {
+        "KOREAN_SYLLABLES" => enabled("HANGUL"),
+        "JAPANESE_KANA" => enabled("KANA"),
+        "EMOJI" => enabled("EMOJI_ZWJ") || !key.contains('\u{200D}'),
+        "CJK_UNIFIED" => {
+            enabled("CJK_EXT")
+                || key.chars().all(|c| {
+                    let cp = u32::from(c);
+                    CJK_CORE.iter().any(|&(lo, hi)| (lo..=hi).contains(&cp))
+                })
+        }
+        _ => true,
+    }
+}
+
+/// Returns the blocks that replace the dataset of table `name`, if its feature is off.
+fn fallback(name: &str) -> Ranges {
+    FALLBACKS
+        .iter()
+        .find(|&&(table, feature, _)| table == name && !enabled(feature))
+        .map_or(&[], |&(_, _, ranges)| ranges)
+}
+
+/// Returns whether cargo feature `feature` (upper case, `_` for `-`) is enabled.
+fn enabled(feature: &str) -> bool {
+    env::var_os(format!("CARGO_FEATURE_{feature}")).is_some()
+}
+
+/// Merges code points into sorted, inclusive ranges.
+fn to_ranges(mut cps: Vec<u32>) -> Vec<(u32, u32)> {
+    cps.sort_unstable();
+    cps.dedup();
+
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for cp in cps {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cp => *end = cp,
+            _ => ranges.push((cp, cp)),
+        }
+    }
+
+    ranges
+}
@@ -0,0 +1,57 @@
+#!/usr/bin/env python3
+
+# === Standard Library ===
+import json
+import argparse
+import unicodedata
+from pathlib import Path
+
+
+# ========================================
+# 📁 Local Project Paths
+# ========================================
+
+ROOT = Path(__file__).resolve().parent.parent
+ASSET_PATH = ROOT / "src" / "assets" / "east_asian_ambiguous.json"
+
+
+def ambiguous_ranges():
+    """
+    Collect the code points with East_Asian_Width=A as inclusive ranges.
+    """
+    ranges = []
+    for cp in range(0x110000):
+        if unicodedata.east_asian_width(chr(cp)) != "A":
+            continue
+        if ranges and ranges[-1][1] + 1 == cp:
+            ranges[-1][1] = cp
+        else:
+            ranges.append([cp, cp])
+    return ranges
+
+
+def main():
+    parser = argparse.ArgumentParser(
+        description="Generate the East Asian Ambiguous table from Python's Unicode database."
+    )
+    parser.add_argument("--output", type=Path, default=ASSET_PATH, help="Output JSON path")
+    args = parser.parse_args()
+
+    ranges = ambiguous_ranges()
+    header = {
+        "unicode_version": unicodedata.unidata_version,
+        "source": "EastAsianWidth.txt (East_Asian_Width=A), via Python unicodedata",
+    }
+
+    # One range per line keeps diffs between Unicode versions readable.
+    lines = [f"  {json.dumps(k)}: {json.dumps(v)}," for k, v in header.items()]
+    lines.append('  "ranges": [')
+    lines.append(",\n".join(f'    ["{lo:04X}", "{hi:04X}"]' for lo, hi in ranges))
+    lines.append("  ]")
+
+    args.output.write_text("{\n" + "\n".join(lines) + "\n}\n")
+    print(f"✅ Wrote {len(ranges)} ranges (Unicode {unicodedata.unidata_version}) to {args.output}")
+
+
+if __name__ == "__main__":
+    main()
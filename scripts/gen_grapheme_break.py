@@ -0,0 +1,97 @@
+#!/usr/bin/env python3
+
+# === Standard Library ===
+import re
+import json
+import argparse
+import subprocess
+from pathlib import Path
+
+
+# ========================================
+# 📁 Local Project Paths
+# ========================================
+
+ROOT = Path(__file__).resolve().parent.parent
+ASSET_PATH = ROOT / "src" / "assets" / "grapheme_break.json"
+
+CHAR = r"'\\u\{([0-9a-fA-F]+)\}'"
+# Entries may be wrapped across lines by rustfmt.
+CLASS_ENTRY = re.compile(rf"\({CHAR},\s+{CHAR},\s+GC_(\w+)\)")
+RANGE_ENTRY = re.compile(rf"\({CHAR},\s+{CHAR}\)")
+SINGLE_CHAR = re.compile(CHAR)
+
+
+def segmentation_tables():
+    """
+    Locate `src/tables.rs` of the unicode-segmentation version locked in Cargo.lock.
+    """
+    metadata = json.loads(
+        subprocess.run(
+            ["cargo", "metadata", "--format-version", "1", "--manifest-path", str(ROOT / "Cargo.toml")],
+            check=True,
+            capture_output=True,
+            text=True,
+        ).stdout
+    )
+    package = next(p for p in metadata["packages"] if p["name"] == "unicode-segmentation")
+    return package["version"], Path(package["manifest_path"]).parent / "src" / "tables.rs"
+
+
+def section(source, start, end="];"):
+    """
+    Return the text between the first `start` marker and the following `end`.
+    """
+    begin = source.index(start)
+    return source[begin:source.index(end, begin)]
+
+
+def hex_range(lo, hi):
+    return [f"{int(lo, 16):04X}", f"{int(hi, 16):04X}"]
+
+
+def main():
+    parser = argparse.ArgumentParser(
+        description="Extract the grapheme break tables used by unicode-segmentation."
+    )
+    parser.add_argument("--tables", type=Path, help="Path to unicode-segmentation's src/tables.rs")
+    parser.add_argument("--output", type=Path, default=ASSET_PATH, help="Output JSON path")
+    args = parser.parse_args()
+
+    version, tables = segmentation_tables() if args.tables is None else ("unknown", args.tables)
+    source = tables.read_text()
+
+    unicode_version = ".".join(
+        re.search(r"pub const UNICODE_VERSION: \(u64, u64, u64\) = \((\d+), (\d+), (\d+)\);", source).groups()
+    )
+    classes = [
+        hex_range(lo, hi) + [name]
+        for lo, hi, name in CLASS_ENTRY.findall(section(source, "grapheme_cat_table: &["))
+    ]
+    incb_extend = [hex_range(lo, hi) for lo, hi in RANGE_ENTRY.findall(section(source, "InCB_Extend_table: &["))]
+    incb_linker = [
+        hex_range(cp, cp) for cp in SINGLE_CHAR.findall(section(source, "fn is_incb_linker", ")\n"))
+    ]
+
+    header = {
+        "unicode_version": unicode_version,
+        "source": f"unicode-segmentation {version} (src/tables.rs), the segmenter used by display_width",
+    }
+
+    # One range per line keeps diffs between Unicode versions readable.
+    def block(key, rows):
+        return [f'  "{key}": [', ",\n".join(f"    {json.dumps(row)}" for row in rows), "  ]"]
+
+    lines = [f"  {json.dumps(k)}: {json.dumps(v)}," for k, v in header.items()]
+    lines += block("classes", classes)
+    lines[-1] += ","
+    lines += block("incb_extend", incb_extend)
+    lines[-1] += ","
+    lines += block("incb_linker", incb_linker)
+
+    args.output.write_text("{\n" + "\n".join(lines) + "\n}\n")
+    print(f"✅ Wrote {len(classes)} class ranges (Unicode {unicode_version}) to {args.output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,104 @@
+//! Alignment-forcing output via absolute cursor positioning.
+//!
+//! Terminals without [text-sizing](crate::encode_text_sizing) support still
+//! disagree on the width of emoji and ambiguous characters. Following each
+//! such grapheme with a move to the column runefix expects confines any
+//! disagreement to that grapheme's own cells: the rest of the line starts
+//! where runefix says it does.
+//!
+//! The move is CHA (`ESC [ n G`, Cursor Horizontal Absolute), which keeps the
+//! cursor on its row, so output does not depend on where the line is printed.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt::Write;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ansi::{Segment, segments};
+use crate::cursor::CursorSim;
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::width::is_contested;
+
+/// Follows each contested grapheme in `line` with a CHA move to the column
+/// after it, assuming the line starts at column `start_col` (0-based).
+///
+/// Contested graphemes are those [`encode_text_sizing`](crate::encode_text_sizing)
+/// wraps in [`TextSizingMode::Contested`](crate::TextSizingMode::Contested).
+/// Other graphemes and escape sequences are copied unchanged, and columns
+/// advance as with [`CursorSim`] (tabs, `\r`, `\b`). The line is assumed to
+/// fit on the terminal row. Returns the input borrowed when no move is needed.
+///
+/// Which graphemes are contested does not depend on the width policy: a
+/// policy sets the columns the moves target, but terminals may still draw
+/// e.g. an emoji measured as 1 column in 2.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{display_width_ansi, render_aligned};
+///
+/// let line = "名前 👍🏽 ok";
+/// let out = render_aligned(line, 4);
+///
+/// assert_eq!(out, "名前 👍🏽\x1b[12G ok");
+/// assert_eq!(display_width_ansi(&out), display_width_ansi(line));
+/// ```
+pub fn render_aligned(line: &str, start_col: usize) -> Cow<'_, str> {
+    render_aligned_impl(
+        line,
+        CursorSim::new(usize::MAX).with_start_column(start_col),
+    )
+}
+
+/// Same as [`render_aligned`], but applies the given [`WidthPolicy`] strategy.
+///
+/// This is the `render_aligned(line, start_col, policy)` form: as elsewhere in
+/// the crate, the policy parameter lives in a `_with_policy` variant. The
+/// policy only changes the target columns, not which graphemes get a move.
+#[cfg(feature = "policy")]
+pub fn render_aligned_with_policy<'a>(
+    line: &'a str,
+    start_col: usize,
+    policy: Option<&WidthPolicy>,
+) -> Cow<'a, str> {
+    render_aligned_impl(
+        line,
+        CursorSim::with_policy(usize::MAX, policy).with_start_column(start_col),
+    )
+}
+
+fn render_aligned_impl(line: &str, mut sim: CursorSim) -> Cow<'_, str> {
+    let any = segments(line).any(|segment| match segment {
+        Segment::Text(text) => text.graphemes(true).any(is_contested),
+        _ => false,
+    });
+
+    if !any {
+        return Cow::Borrowed(line);
+    }
+
+    let mut out = String::with_capacity(line.len() + 16);
+
+    for segment in segments(line) {
+        match segment {
+            Segment::Text(text) => {
+                for g in text.graphemes(true) {
+                    out.push_str(g);
+
+                    if sim.put(g).is_some() && is_contested(g) {
+                        let _ = write!(out, "\x1b[{}G", sim.column() + 1);
+                    }
+                }
+            }
+            Segment::Sized(sizing) => {
+                out.push_str(sizing.raw);
+                sim.put_sized(&sizing);
+            }
+            Segment::Escape(escape) => out.push_str(escape),
+        }
+    }
+
+    Cow::Owned(out)
+}
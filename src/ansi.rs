@@ -0,0 +1,325 @@
+//! ANSI escape sequence aware measurement.
+//!
+//! Terminal output is full of SGR color codes such as `\x1b[31m`. Only the
+//! ESC itself is a control character, so plain [`display_width`](crate::display_width)
+//! counts `[31m` as four visible columns. This module recognizes escape
+//! sequences per ECMA-48 and treats them as zero-width:
+//!
+//! - CSI – `ESC [` parameters, intermediates, final byte (SGR, cursor movement)
+//! - OSC – `ESC ]` … terminated by BEL or ST (titles, OSC 8 hyperlinks)
+//! - DCS, SOS, PM, APC – `ESC P` / `ESC X` / `ESC ^` / `ESC _` … terminated by ST
+//! - SS2, SS3 – `ESC N` / `ESC O` followed by one character
+//! - Other escape sequences – `ESC`, intermediates, final byte (e.g. `ESC 7`)
+//!
+//! The 8-bit C1 forms of the introducers (e.g. U+009B for CSI) are recognized
+//! too. An unterminated sequence extends to the end of the input.
+//!
+//! Kitty text-sizing sequences (OSC 66, see [`encode_text_sizing`](crate::encode_text_sizing))
+//! are the exception: they print their text, and count as the number of cells
+//! they declare.
+//!
+//! - [`display_width_ansi`] – Width of the visible text
+//! - [`strip_ansi`] – Removes all escape sequences
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::grapheme::display_width;
+#[cfg(feature = "policy")]
+use crate::grapheme::policy_ext::display_width_with_policy;
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+
+const ESC: u8 = 0x1B;
+const BEL: u8 = 0x07;
+
+/// A run of visible text or a single escape sequence, borrowed from the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Segment<'a> {
+    Text(&'a str),
+    Escape(&'a str),
+    Sized(TextSizing<'a>),
+}
+
+impl<'a> Segment<'a> {
+    /// Returns the segment's text, as it appears in the input.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn as_str(&self) -> &'a str {
+        match *self {
+            Segment::Text(s) | Segment::Escape(s) => s,
+            Segment::Sized(sizing) => sizing.raw,
+        }
+    }
+}
+
+/// A kitty text-sizing sequence: `OSC 66 ; metadata ; text ST`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TextSizing<'a> {
+    /// The whole sequence, as it appears in the input.
+    pub(crate) raw: &'a str,
+
+    /// The text drawn in the sized block.
+    pub(crate) text: &'a str,
+
+    /// Byte offset of `text` in `raw`.
+    text_start: usize,
+
+    /// Scale factor (`s`, 1–7).
+    scale: usize,
+
+    /// Width in scaled cells (`w`, 0–7), or `0` to use the width of `text`.
+    width: usize,
+}
+
+impl<'a> TextSizing<'a> {
+    /// Parses an OSC 66 sequence, returning `None` for any other escape
+    /// sequence or malformed metadata.
+    fn parse(raw: &'a str) -> Option<Self> {
+        let prefix = ["\x1b]66;", "\u{9d}66;"]
+            .into_iter()
+            .find(|prefix| raw.starts_with(prefix))?;
+        let body = &raw[prefix.len()..];
+        let body = ["\x07", "\x1b\\", "\u{9c}"]
+            .into_iter()
+            .find_map(|st| body.strip_suffix(st))
+            .unwrap_or(body);
+        let (metadata, text) = body.split_once(';')?;
+
+        let mut sizing = Self {
+            raw,
+            text,
+            text_start: prefix.len() + metadata.len() + 1,
+            scale: 1,
+            width: 0,
+        };
+
+        for pair in metadata.split(':').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=')?;
+            let value: usize = value.parse().ok()?;
+
+            match key {
+                "s" => sizing.scale = value.clamp(1, 7),
+                "w" => sizing.width = value.min(7),
+                _ => {}
+            }
+        }
+
+        Some(sizing)
+    }
+
+    /// Returns the number of columns the sized block occupies.
+    pub(crate) fn cells(&self, width_of: &dyn Fn(&str) -> usize) -> usize {
+        let width = match self.width {
+            0 => self.text.graphemes(true).map(width_of).sum(),
+            w => w,
+        };
+        self.scale * width
+    }
+
+    /// Returns the parts of the sequence before and after the text.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn delimiters(&self) -> (&'a str, &'a str) {
+        let end = self.text_start + self.text.len();
+        (&self.raw[..self.text_start], &self.raw[end..])
+    }
+}
+
+/// Iterator splitting a string into [`Segment`]s.
+#[derive(Debug, Clone)]
+pub(crate) struct Segments<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+/// Splits `s` into runs of visible text and escape sequences.
+pub(crate) fn segments(s: &str) -> Segments<'_> {
+    Segments { s, pos: 0 }
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.s.as_bytes();
+        let start = self.pos;
+
+        if start >= bytes.len() {
+            return None;
+        }
+
+        if let Some(len) = escape_len(bytes, start) {
+            self.pos += len;
+            let raw = &self.s[start..self.pos];
+            return Some(TextSizing::parse(raw).map_or(Segment::Escape(raw), Segment::Sized));
+        }
+
+        let mut end = start + 1;
+        while end < bytes.len() && escape_len(bytes, end).is_none() {
+            end += 1;
+        }
+
+        // `end` is at an introducer or the end, both of which are char boundaries.
+        self.pos = end;
+        Some(Segment::Text(&self.s[start..end]))
+    }
+}
+
+/// The kinds of sequence an introducer starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Control Sequence Introducer.
+    Csi,
+
+    /// Control string (OSC, DCS, SOS, PM, APC); OSC may also end with BEL.
+    String { osc: bool },
+
+    /// Single shift (SS2, SS3): one following character.
+    SingleShift,
+
+    /// Any other escape sequence: intermediates and a final byte.
+    Escape,
+}
+
+/// Returns the kind and length of the sequence introducer at `i`, if any.
+fn introducer(bytes: &[u8], i: usize) -> Option<(Kind, usize)> {
+    match (bytes[i], bytes.get(i + 1).copied()) {
+        (ESC, next) => Some(match next {
+            Some(b'[') => (Kind::Csi, 2),
+            Some(b']') => (Kind::String { osc: true }, 2),
+            Some(b'P' | b'X' | b'^' | b'_') => (Kind::String { osc: false }, 2),
+            Some(b'N' | b'O') => (Kind::SingleShift, 2),
+            _ => (Kind::Escape, 1),
+        }),
+        // 8-bit C1 introducers, UTF-8 encoded as `C2 xx`.
+        (0xC2, Some(0x9B)) => Some((Kind::Csi, 2)),
+        (0xC2, Some(0x9D)) => Some((Kind::String { osc: true }, 2)),
+        (0xC2, Some(0x90 | 0x98 | 0x9E | 0x9F)) => Some((Kind::String { osc: false }, 2)),
+        (0xC2, Some(0x8E | 0x8F)) => Some((Kind::SingleShift, 2)),
+        _ => None,
+    }
+}
+
+/// Returns the byte length of the escape sequence starting at `i`, if any.
+pub(crate) fn escape_len(bytes: &[u8], i: usize) -> Option<usize> {
+    let (kind, intro) = introducer(bytes, i)?;
+    let mut j = i + intro;
+
+    match kind {
+        Kind::Csi => {
+            while j < bytes.len() && matches!(bytes[j], 0x30..=0x3F) {
+                j += 1;
+            }
+            while j < bytes.len() && matches!(bytes[j], 0x20..=0x2F) {
+                j += 1;
+            }
+            // A malformed sequence ends before the offending byte.
+            if j < bytes.len() && matches!(bytes[j], 0x40..=0x7E) {
+                j += 1;
+            }
+        }
+        Kind::String { osc } => {
+            while j < bytes.len() {
+                match (bytes[j], bytes.get(j + 1).copied()) {
+                    (BEL, _) if osc => return Some(j + 1 - i),
+                    (ESC, Some(b'\\')) | (0xC2, Some(0x9C)) => return Some(j + 2 - i),
+                    // Any other ESC aborts the string and starts a new sequence.
+                    (ESC, _) => break,
+                    _ => j += 1,
+                }
+            }
+        }
+        Kind::SingleShift => {
+            if let Some(len) = bytes.get(j).and_then(utf8_len) {
+                j = (j + len).min(bytes.len());
+            }
+        }
+        Kind::Escape => {
+            while j < bytes.len() && matches!(bytes[j], 0x20..=0x2F) {
+                j += 1;
+            }
+            if j < bytes.len() && matches!(bytes[j], 0x30..=0x7E) {
+                j += 1;
+            }
+        }
+    }
+
+    Some(j - i)
+}
+
+/// Returns the length of the UTF-8 sequence starting with byte `b`.
+fn utf8_len(b: &u8) -> Option<usize> {
+    match b {
+        0x00..=0x7F => Some(1),
+        0xC0..=0xDF => Some(2),
+        0xE0..=0xEF => Some(3),
+        0xF0..=0xF7 => Some(4),
+        _ => None,
+    }
+}
+
+/// Returns the display width of `s`, ignoring ANSI escape sequences.
+///
+/// Text between escape sequences is measured as with [`display_width`]. Kitty
+/// text-sizing sequences count as the cells they declare.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{display_width, display_width_ansi};
+///
+/// let s = "\x1b[31mred\x1b[0m \x1b]8;;https://example.com\x1b\\链接\x1b]8;;\x1b\\";
+///
+/// assert_eq!(display_width_ansi(s), 8);
+/// assert!(display_width(s) > 8);
+/// ```
+pub fn display_width_ansi(s: &str) -> usize {
+    display_width_ansi_impl(s, &display_width)
+}
+
+/// Same as [`display_width_ansi`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn display_width_ansi_with_policy(s: &str, policy: Option<&WidthPolicy>) -> usize {
+    display_width_ansi_impl(s, &|text| display_width_with_policy(text, policy))
+}
+
+fn display_width_ansi_impl(s: &str, width_of: &dyn Fn(&str) -> usize) -> usize {
+    segments(s)
+        .map(|segment| match segment {
+            Segment::Text(text) => width_of(text),
+            Segment::Escape(_) => 0,
+            Segment::Sized(sizing) => sizing.cells(width_of),
+        })
+        .sum()
+}
+
+/// Removes all ANSI escape sequences from `s`.
+///
+/// The text of kitty text-sizing sequences is kept. Returns the input borrowed
+/// when it contains no escape sequences.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::strip_ansi;
+///
+/// assert_eq!(strip_ansi("\x1b[1;32mok\x1b[0m 完了"), "ok 完了");
+/// assert_eq!(strip_ansi("plain"), "plain");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn strip_ansi(s: &str) -> Cow<'_, str> {
+    match segments(s).next() {
+        None => Cow::Borrowed(s),
+        Some(Segment::Text(text)) if text.len() == s.len() => Cow::Borrowed(s),
+        Some(_) => Cow::Owned(
+            segments(s)
+                .filter_map(|segment| match segment {
+                    Segment::Text(text) => Some(text),
+                    Segment::Escape(_) => None,
+                    Segment::Sized(sizing) => Some(sizing.text),
+                })
+                .collect::<String>(),
+        ),
+    }
+}
@@ -5,6 +5,8 @@
 //!
 //! This helps solve alignment bugs in monospaced environments caused by emoji and CJK widths.
 
+use alloc::vec::Vec;
+
 use crate::RuneDisplayWidth;
 
 /// Splits the input string into **layout atoms** — visual units used for width-aware layout.
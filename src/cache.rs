@@ -0,0 +1,328 @@
+//! Opt-in memoization of display widths.
+//!
+//! TUI frame redraws measure the same labels over and over. [`WidthCache`]
+//! remembers the width of whole strings in a bounded LRU, keyed by string and
+//! policy. On a miss, the string is measured through a per-thread grapheme
+//! cache placed in front of the rule lookups, so repeated clusters (emoji,
+//! CJK) are resolved once per thread.
+//!
+//! The cache is `Send + Sync` and can be shared across threads, e.g. in an
+//! `Arc` or a `static`. Large caches are split into shards with their own
+//! lock and LRU order, and grapheme lookups never lock. Hit and miss counts
+//! are exposed via [`CacheStats`].
+
+use std::boxed::Box;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::fast::display_width_fast;
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+#[cfg(not(feature = "policy"))]
+use crate::width::{get_display_width, get_fallback_width};
+
+/// Identifies the policy an entry was measured with (see `WidthPolicy::as_tuple`).
+type PolicyKey = (usize, usize, usize, usize);
+
+/// Key of the default terminal strategy.
+#[cfg(not(feature = "policy"))]
+const TERMINAL: PolicyKey = (2, 2, 2, 1);
+
+/// Distinct graphemes remembered per thread and policy before starting over.
+const GRAPHEME_CAPACITY: usize = 4096;
+
+/// Smallest capacity given to a shard; smaller caches keep an exact LRU order.
+const SHARD_CAPACITY: usize = 256;
+
+/// Upper bound on the number of shards.
+const MAX_SHARDS: usize = 16;
+
+std::thread_local! {
+    /// Grapheme widths resolved on this thread, shared by all caches since a
+    /// width only depends on the policy.
+    static GRAPHEMES: RefCell<HashMap<PolicyKey, HashMap<Box<str>, usize>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Hit and miss counters of a [`WidthCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Strings whose width was found in the cache.
+    pub hits: u64,
+
+    /// Strings that had to be measured.
+    pub misses: u64,
+
+    /// Graphemes whose width was found in the per-thread grapheme cache.
+    pub grapheme_hits: u64,
+
+    /// Graphemes that had to be resolved through the width rules.
+    pub grapheme_misses: u64,
+
+    /// Number of strings currently cached.
+    pub len: usize,
+}
+
+impl CacheStats {
+    /// Returns the fraction of string lookups served from the cache (`0.0` if none).
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+/// A bounded least-recently-used map from `(policy, text)` to width.
+#[derive(Debug, Default)]
+struct Lru {
+    capacity: usize,
+    len: usize,
+    tick: u64,
+    entries: HashMap<PolicyKey, HashMap<Arc<str>, (usize, u64)>>,
+
+    /// Entries ordered by last use, oldest first, sharing the keys of `entries`.
+    order: BTreeMap<u64, (PolicyKey, Arc<str>)>,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    fn get(&mut self, policy: PolicyKey, s: &str) -> Option<usize> {
+        let (width, used) = self.entries.get_mut(&policy)?.get_mut(s)?;
+
+        self.tick += 1;
+
+        if let Some(key) = self.order.remove(used) {
+            self.order.insert(self.tick, key);
+        }
+
+        *used = self.tick;
+        Some(*width)
+    }
+
+    fn insert(&mut self, policy: PolicyKey, s: &str, width: usize) {
+        // Another thread may have measured the same string meanwhile.
+        if self.capacity == 0
+            || self
+                .entries
+                .get(&policy)
+                .is_some_and(|map| map.contains_key(s))
+        {
+            return;
+        }
+
+        while self.len >= self.capacity {
+            let Some((_, (policy, s))) = self.order.pop_first() else {
+                break;
+            };
+
+            if let Some(map) = self.entries.get_mut(&policy) {
+                map.remove(&s);
+            }
+
+            self.len -= 1;
+        }
+
+        let key: Arc<str> = s.into();
+        self.tick += 1;
+        self.entries
+            .entry(policy)
+            .or_default()
+            .insert(key.clone(), (width, self.tick));
+        self.order.insert(self.tick, (policy, key));
+        self.len += 1;
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.len = 0;
+    }
+}
+
+/// A thread-safe, bounded cache of display widths.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::WidthCache;
+///
+/// let cache = WidthCache::new(1024);
+///
+/// for _ in 0..3 {
+///     assert_eq!(cache.display_width("Hi 👋 世界"), 10);
+/// }
+///
+/// let stats = cache.stats();
+/// assert_eq!((stats.hits, stats.misses), (2, 1));
+/// ```
+#[derive(Debug)]
+pub struct WidthCache {
+    capacity: usize,
+    shards: Box<[Mutex<Lru>]>,
+    hasher: RandomState,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    grapheme_hits: AtomicU64,
+    grapheme_misses: AtomicU64,
+}
+
+impl WidthCache {
+    /// Creates a cache holding up to `capacity` strings.
+    pub fn new(capacity: usize) -> Self {
+        let count = (capacity / SHARD_CAPACITY).clamp(1, MAX_SHARDS);
+
+        // Spread the capacity so that the shards add up to exactly `capacity`.
+        let shards = (0..count)
+            .map(|i| {
+                Mutex::new(Lru::new(
+                    capacity / count + usize::from(i < capacity % count),
+                ))
+            })
+            .collect();
+
+        Self {
+            capacity,
+            shards,
+            hasher: RandomState::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            grapheme_hits: AtomicU64::new(0),
+            grapheme_misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the display width of `s`, as [`display_width`](crate::display_width) would.
+    #[cfg(not(feature = "policy"))]
+    pub fn display_width(&self, s: &str) -> usize {
+        self.measure(s, TERMINAL, get_fallback_width(), &get_display_width)
+    }
+
+    /// Returns the display width of `s`, as [`display_width`](crate::display_width) would.
+    #[cfg(feature = "policy")]
+    pub fn display_width(&self, s: &str) -> usize {
+        self.display_width_with_policy(s, None)
+    }
+
+    /// Same as [`display_width`](Self::display_width), but applies the given [`WidthPolicy`] strategy.
+    #[cfg(feature = "policy")]
+    pub fn display_width_with_policy(&self, s: &str, policy: Option<&WidthPolicy>) -> usize {
+        let terminal = WidthPolicy::terminal();
+        let policy = policy.unwrap_or(&terminal);
+
+        self.measure(s, policy.as_tuple(), policy.fallback, &|g| {
+            policy.resolve_width(g)
+        })
+    }
+
+    /// Returns a snapshot of the hit and miss counters.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            grapheme_hits: self.grapheme_hits.load(Ordering::Relaxed),
+            grapheme_misses: self.grapheme_misses.load(Ordering::Relaxed),
+            len: self.shards.iter().map(|shard| lock(shard).len).sum(),
+        }
+    }
+
+    /// Resets the hit and miss counters, keeping cached entries.
+    pub fn reset_stats(&self) {
+        for counter in [
+            &self.hits,
+            &self.misses,
+            &self.grapheme_hits,
+            &self.grapheme_misses,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Removes all cached strings.
+    ///
+    /// Grapheme widths are kept: they are per thread and never go stale.
+    pub fn clear(&self) {
+        for shard in &self.shards {
+            lock(shard).clear();
+        }
+    }
+
+    fn measure(
+        &self,
+        s: &str,
+        policy: PolicyKey,
+        fallback: usize,
+        resolve: &dyn Fn(&str) -> usize,
+    ) -> usize {
+        let shard = self.shard(policy, s);
+
+        if let Some(width) = shard.and_then(|shard| lock(shard).get(policy, s)) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return width;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        // No lock is held while measuring, so other threads are not blocked
+        // by a slow measurement.
+        let (mut hits, mut misses) = (0, 0);
+
+        let width = GRAPHEMES.with_borrow_mut(|graphemes| {
+            let widths = graphemes.entry(policy).or_default();
+
+            display_width_fast(s, fallback, |g| {
+                if let Some(&w) = widths.get(g) {
+                    hits += 1;
+                    return w;
+                }
+
+                misses += 1;
+
+                if widths.len() >= GRAPHEME_CAPACITY {
+                    widths.clear();
+                }
+
+                let w = resolve(g);
+                widths.insert(g.into(), w);
+                w
+            })
+        });
+
+        if hits > 0 {
+            self.grapheme_hits.fetch_add(hits, Ordering::Relaxed);
+        }
+
+        if misses > 0 {
+            self.grapheme_misses.fetch_add(misses, Ordering::Relaxed);
+        }
+
+        if let Some(shard) = shard {
+            lock(shard).insert(policy, s, width);
+        }
+
+        width
+    }
+
+    /// Returns the shard responsible for `s`, or `None` if nothing is cached.
+    fn shard(&self, policy: PolicyKey, s: &str) -> Option<&Mutex<Lru>> {
+        match &*self.shards {
+            _ if self.capacity == 0 => None,
+            [shard] => Some(shard),
+            shards => Some(&shards[self.hasher.hash_one((policy, s)) as usize % shards.len()]),
+        }
+    }
+}
+
+fn lock(shard: &Mutex<Lru>) -> MutexGuard<'_, Lru> {
+    // The LRU stays consistent even if a holder panicked.
+    shard.lock().unwrap_or_else(PoisonError::into_inner)
+}
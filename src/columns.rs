@@ -0,0 +1,130 @@
+//! `Display` adapter that makes std format specifiers column-correct.
+//!
+//! Rust's formatting machinery counts `char`s for width and precision, so
+//! `format!("{:>8}", "世界")` pads to 8 *chars* — 10 columns in a terminal.
+//! Wrapping the text in [`columns`] reinterprets the specifiers in display
+//! columns:
+//!
+//! - **width** (`{:8}`) – minimum display width, padded with the fill character
+//! - **precision** (`{:.8}`) – maximum display width, truncated at grapheme boundaries
+//! - **fill / align** (`{:*^8}`) – honored as usual; wide fill characters are supported
+//!
+//! ```rust
+//! use runefix_core::columns;
+//!
+//! assert_eq!(format!("{:>6}|", columns("世界")), "  世界|");
+//! assert_eq!(format!("{:-^8.5}|", columns("你好世界")), "--你好--|");
+//! ```
+
+use core::fmt::{self, Alignment, Display, Formatter};
+
+use crate::pad::{Align, pad_impl};
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::truncate::{TruncateOptions, truncate_with_impl};
+#[cfg(not(feature = "policy"))]
+use crate::width::get_display_width;
+#[cfg(feature = "policy")]
+use crate::width::get_display_width_with_policy;
+
+/// A string wrapper whose [`Display`] impl measures width and precision in columns.
+///
+/// Created by [`columns`] (or `columns_with_policy` with the `policy` feature).
+/// Without any format specifiers, it prints the text unchanged.
+#[derive(Debug, Clone, Copy)]
+pub struct Columns<'a> {
+    s: &'a str,
+
+    #[cfg(feature = "policy")]
+    policy: Option<&'a WidthPolicy>,
+}
+
+/// Wraps a string so that std format width and precision count display columns.
+///
+/// # Arguments
+///
+/// * `s` - The text to format
+///
+/// # Returns
+///
+/// A [`Columns`] adapter implementing [`Display`].
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::columns;
+///
+/// let rows = [("名前", "値"), ("id", "42")];
+/// for (k, v) in rows {
+///     println!("{:<6}|{:>4}", columns(k), columns(v));
+/// }
+/// assert_eq!(format!("{:<6}|", columns("名前")), "名前  |");
+/// ```
+pub fn columns(s: &str) -> Columns<'_> {
+    Columns {
+        s,
+        #[cfg(feature = "policy")]
+        policy: None,
+    }
+}
+
+/// Same as [`columns`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn columns_with_policy<'a>(s: &'a str, policy: Option<&'a WidthPolicy>) -> Columns<'a> {
+    Columns { s, policy }
+}
+
+impl Columns<'_> {
+    /// Returns the wrapped text.
+    pub fn as_str(&self) -> &str {
+        self.s
+    }
+
+    /// Writes the text honoring width, precision, fill, and alignment in columns.
+    fn fmt_with<F>(&self, f: &mut Formatter<'_>, width_of: &F) -> fmt::Result
+    where
+        F: Fn(&str) -> usize,
+    {
+        let text = match f.precision() {
+            Some(max_width) => {
+                let opts = TruncateOptions::end().with_ellipsis("");
+                truncate_with_impl(self.s, max_width, &opts, width_of).text
+            }
+            None => self.s.into(),
+        };
+
+        let Some(width) = f.width() else {
+            return f.write_str(&text);
+        };
+
+        // Strings are left-aligned by default, as in std.
+        let align = match f.align() {
+            Some(Alignment::Right) => Align::Right,
+            Some(Alignment::Center) => Align::Center,
+            Some(Alignment::Left) | None => Align::Left,
+        };
+
+        let mut fill = [0; 4];
+        let fill = f.fill().encode_utf8(&mut fill);
+
+        f.write_str(&pad_impl(&text, width, fill, align, width_of))
+    }
+}
+
+impl Display for Columns<'_> {
+    #[cfg(not(feature = "policy"))]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, &get_display_width)
+    }
+
+    #[cfg(feature = "policy")]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, &|g| get_display_width_with_policy(g, self.policy))
+    }
+}
+
+impl AsRef<str> for Columns<'_> {
+    fn as_ref(&self) -> &str {
+        self.s
+    }
+}
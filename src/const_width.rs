@@ -0,0 +1,173 @@
+//! Compile-time width computation.
+//!
+//! - [`char_width`] – `const fn` width of a single `char`
+//! - [`display_width_const`] – `const fn` width of a string
+//! - [`width!`](crate::width!) – evaluates a string literal's width as a constant
+//!
+//! These use the default terminal strategy and read the same static tables as
+//! [`display_width`](crate::display_width), so fixed layouts (table headers,
+//! column widths) can be computed and checked by the compiler.
+//!
+//! Grapheme clusters are segmented by the extended grapheme cluster rules of
+//! UAX #29, with break tables taken from the same `unicode-segmentation`
+//! release that the runtime API uses, so both agree on cluster boundaries.
+
+use crate::rules::grapheme_break::{GraphemeBreak, grapheme_break, is_incb_extend, is_incb_linker};
+use crate::rules::tables::{EMOJI, WIDE_CHARS, decode, in_ranges};
+
+/// Returns the display width of a single `char` for terminal environments.
+///
+/// Same as [`rune_width`](crate::RuneDisplayWidth::rune_width), but usable in
+/// `const` contexts:
+///
+/// - Control characters → `0`
+/// - ASCII → `1`
+/// - CJK, Kana, Hangul, fullwidth variants and punctuation, single-codepoint emoji → `2`
+/// - Anything else → `1`
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::char_width;
+///
+/// const CJK: u8 = char_width('語');
+///
+/// assert_eq!(CJK, 2);
+/// assert_eq!(char_width('a'), 1);
+/// assert_eq!(char_width('\t'), 0);
+/// ```
+pub const fn char_width(c: char) -> u8 {
+    let cp = c as u32;
+
+    if cp < 0x20 || (cp >= 0x7F && cp <= 0x9F) {
+        return 0;
+    }
+
+    if cp <= 0x7F {
+        return 1;
+    }
+
+    if in_ranges(cp, WIDE_CHARS) { 2 } else { 1 }
+}
+
+/// Returns the display width of a string for terminal environments, in a `const fn`.
+///
+/// Matches [`display_width`](crate::display_width): graphemes are segmented
+/// by the extended grapheme cluster rules of UAX #29 and measured with the
+/// same tables. Prefer the [`width!`](crate::width!) macro for string literals.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::display_width_const;
+///
+/// const HEADER: usize = display_width_const("見出し 👩‍💻");
+///
+/// assert_eq!(HEADER, 9);
+/// ```
+pub const fn display_width_const(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut width = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let end = cluster_end(bytes, i);
+        let (c, len) = decode(bytes, i);
+
+        width += if i + len == end {
+            char_width(c) as usize
+        } else if EMOJI.contains(bytes.split_at(end).0.split_at(i).1) {
+            2
+        } else {
+            1
+        };
+
+        i = end;
+    }
+
+    width
+}
+
+/// Evaluates the display width of a string at compile time.
+///
+/// Expands to a `usize` constant computed by [`display_width_const`], so it can
+/// be used in `const` items and array lengths.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::width;
+///
+/// const TITLE: usize = width!("見出し");
+/// let underline = ['─'; width!("見出し")];
+///
+/// assert_eq!(TITLE, 6);
+/// assert_eq!(underline.len(), 6);
+/// ```
+#[macro_export]
+macro_rules! width {
+    ($s:expr) => {{
+        const WIDTH: usize = $crate::display_width_const($s);
+        WIDTH
+    }};
+}
+
+/// Returns the byte offset where the grapheme cluster starting at `start` ends.
+///
+/// Applies rules GB3 to GB13 of UAX #29. The context that GB9c, GB11, and
+/// GB12/13 look back for is tracked while scanning forward.
+const fn cluster_end(bytes: &[u8], start: usize) -> usize {
+    use GraphemeBreak::*;
+
+    let mut i = start;
+    let mut prev = Other;
+
+    // A consonant followed by conjunct extenders, and whether a linker was among them (GB9c).
+    let mut consonant = false;
+    let mut linked = false;
+
+    // Ends with `ExtPict Extend*`, and with `ExtPict Extend* ZWJ` (GB11).
+    let mut pictographic = false;
+    let mut pictographic_zwj = false;
+
+    // An odd number of regional indicators so far (GB12, GB13).
+    let mut odd_regional = false;
+
+    while i < bytes.len() {
+        let (c, len) = decode(bytes, i);
+        let cp = c as u32;
+        let class = grapheme_break(cp);
+
+        let joins = match (prev, class) {
+            (Cr, Lf) => true,
+            (Cr | Lf | Control, _) | (_, Cr | Lf | Control) => false,
+            (L, L | V | Lv | Lvt) | (Lv | V, V | T) | (Lvt | T, T) => true,
+            (_, Extend | Zwj | SpacingMark) | (Prepend, _) => true,
+            (_, InCbConsonant) => linked,
+            (Zwj, ExtendedPictographic) => pictographic_zwj,
+            (RegionalIndicator, RegionalIndicator) => odd_regional,
+            _ => false,
+        };
+
+        if i > start && !joins {
+            break;
+        }
+
+        if is_incb_linker(cp) {
+            linked = consonant;
+        } else if !is_incb_extend(cp) {
+            consonant = matches!(class, InCbConsonant);
+            linked = false;
+        }
+
+        pictographic_zwj = pictographic && matches!(class, Zwj);
+        pictographic =
+            matches!(class, ExtendedPictographic) || (pictographic && matches!(class, Extend));
+        odd_regional = matches!(class, RegionalIndicator) && !odd_regional;
+
+        prev = class;
+        i += len;
+    }
+
+    i
+}
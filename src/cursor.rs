@@ -0,0 +1,275 @@
+//! Cursor position prediction for terminal output.
+//!
+//! Line editors and progress renderers redraw by moving the cursor back to
+//! where their output started, so they must know exactly where printing left
+//! it. Besides grapheme widths, that depends on how the terminal treats the
+//! right margin (auto-wrap mode, DECAWM):
+//!
+//! - Printing into the last column does not wrap yet. The cursor stays on that
+//!   column with a *pending wrap*, and the next printable grapheme wraps first.
+//! - A wide grapheme that does not fit in the remaining columns wraps early,
+//!   leaving the last cell of the row blank.
+//!
+//! [`CursorSim`] follows xterm, which most terminal emulators match.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ansi::{Segment, TextSizing, segments};
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+#[cfg(not(feature = "policy"))]
+use crate::width::get_display_width;
+
+/// Simulates the cursor of an auto-wrapping terminal with a fixed number of columns.
+///
+/// Rows are counted from the row the cursor starts on. Control characters
+/// move the cursor as follows:
+///
+/// - `\r` – to column 0
+/// - `\n` – to the next row, and to column 0 unless [`with_onlcr(false)`](Self::with_onlcr)
+/// - `\b` – one column left, stopping at column 0
+/// - `\t` – to the next tab stop, stopping at the last column
+///
+/// `\r`, `\n`, and `\b` cancel a pending wrap; `\t` leaves the cursor in place.
+/// Other control characters and ANSI escape sequences are zero-width and do
+/// not move the cursor; cursor movement sequences (`ESC [ A`, …) are not
+/// interpreted. Kitty text-sizing sequences advance by the cells they declare.
+///
+/// The terminal is assumed never to scroll horizontally, and rows are never
+/// limited, so scrolling does not affect the result.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::CursorSim;
+///
+/// // A 10-column terminal, printing from column 6.
+/// let mut sim = CursorSim::new(10).with_start_column(6);
+/// sim.print("abc中");
+///
+/// // `中` does not fit in column 9 and wraps early.
+/// assert_eq!(sim.position(), (1, 2));
+/// assert_eq!(sim.rows(), 2);
+///
+/// // Filling the row exactly leaves a pending wrap, not a new row.
+/// let mut sim = CursorSim::new(4);
+/// sim.print("文字");
+/// assert_eq!(sim.position(), (0, 3));
+/// assert!(sim.is_wrap_pending());
+/// assert_eq!(sim.rows(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorSim {
+    /// Terminal width in columns (at least 1).
+    columns: usize,
+
+    /// Distance between tab stops (at least 1).
+    tab_width: usize,
+
+    /// Whether `\n` also returns to column 0 (the tty `onlcr` output mode).
+    onlcr: bool,
+
+    /// Current row, relative to the starting row.
+    row: usize,
+
+    /// Current column, in `0..columns`.
+    col: usize,
+
+    /// Whether the cursor sits on the last column with a deferred wrap.
+    pending_wrap: bool,
+
+    #[cfg(feature = "policy")]
+    policy: WidthPolicy,
+}
+
+impl CursorSim {
+    /// Creates a simulator for a terminal `columns` wide, with the cursor at row 0, column 0.
+    ///
+    /// Tab stops are every 8 columns and `\n` returns to column 0, as with
+    /// a terminal in its default modes. A width of `0` is treated as `1`.
+    pub fn new(columns: usize) -> Self {
+        Self {
+            columns: columns.max(1),
+            tab_width: 8,
+            onlcr: true,
+            row: 0,
+            col: 0,
+            pending_wrap: false,
+            #[cfg(feature = "policy")]
+            policy: WidthPolicy::terminal(),
+        }
+    }
+
+    /// Creates a simulator using the given [`WidthPolicy`] strategy.
+    #[cfg(feature = "policy")]
+    pub fn with_policy(columns: usize, policy: Option<&WidthPolicy>) -> Self {
+        Self {
+            policy: policy.cloned().unwrap_or_else(WidthPolicy::terminal),
+            ..Self::new(columns)
+        }
+    }
+
+    /// Moves the cursor to `col` on the starting row, clamped to the last column.
+    pub fn with_start_column(mut self, col: usize) -> Self {
+        self.col = col.min(self.columns - 1);
+        self.pending_wrap = false;
+        self
+    }
+
+    /// Sets the distance between tab stops. A width of `0` is treated as `1`.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
+    /// Sets whether `\n` also returns to column 0.
+    ///
+    /// This matches the tty `onlcr` output flag, which is on by default but
+    /// off when a program disables output processing (e.g. `cfmakeraw`).
+    pub fn with_onlcr(mut self, onlcr: bool) -> Self {
+        self.onlcr = onlcr;
+        self
+    }
+
+    /// Prints `s` and advances the cursor.
+    pub fn print(&mut self, s: &str) -> &mut Self {
+        for segment in segments(s) {
+            match segment {
+                Segment::Text(text) => {
+                    for g in text.graphemes(true) {
+                        self.put(g);
+                    }
+                }
+                Segment::Sized(sizing) => {
+                    self.put_sized(&sizing);
+                }
+                Segment::Escape(_) => {}
+            }
+        }
+        self
+    }
+
+    /// Returns the cursor position as `(row, column)`, with rows counted from the starting row.
+    pub fn position(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// Returns the current row, counted from the starting row.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Returns the current column.
+    pub fn column(&self) -> usize {
+        self.col
+    }
+
+    /// Returns the number of rows printed on or moved through, including the starting row.
+    ///
+    /// A pending wrap does not count the next row until something is printed on it.
+    pub fn rows(&self) -> usize {
+        self.row + 1
+    }
+
+    /// Returns `true` if the cursor is on the last column and the next
+    /// printable grapheme will wrap first.
+    pub fn is_wrap_pending(&self) -> bool {
+        self.pending_wrap
+    }
+
+    /// Returns the terminal width in columns.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Moves the cursor to `(row, col)`, clamping the column to the last one.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn set_position(&mut self, row: usize, col: usize) {
+        self.row = row;
+        self.col = col.min(self.columns - 1);
+        self.pending_wrap = false;
+    }
+
+    /// Sets the current row, keeping the column and any pending wrap.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn set_row(&mut self, row: usize) {
+        self.row = row;
+    }
+
+    /// Applies one grapheme and returns where it was printed, as
+    /// `(row, column, width)`, if it occupies any cells.
+    pub(crate) fn put(&mut self, g: &str) -> Option<(usize, usize, usize)> {
+        match g {
+            "\r" => self.carriage_return(),
+            "\n" => self.line_feed(),
+            "\r\n" => {
+                self.carriage_return();
+                self.line_feed();
+            }
+            "\x08" => {
+                self.col = self.col.saturating_sub(1);
+                self.pending_wrap = false;
+            }
+            "\t" => {
+                if !self.pending_wrap {
+                    let next = (self.col / self.tab_width + 1) * self.tab_width;
+                    self.col = next.min(self.columns - 1);
+                }
+            }
+            _ => return self.advance(self.width_of(g)),
+        }
+        None
+    }
+
+    /// Applies a kitty text-sizing sequence as one block of the cells it declares.
+    pub(crate) fn put_sized(&mut self, sizing: &TextSizing) -> Option<(usize, usize, usize)> {
+        self.advance(sizing.cells(&|g| self.width_of(g)))
+    }
+
+    fn carriage_return(&mut self) {
+        self.col = 0;
+        self.pending_wrap = false;
+    }
+
+    fn line_feed(&mut self) {
+        if self.onlcr {
+            self.col = 0;
+        }
+        self.pending_wrap = false;
+        self.row += 1;
+    }
+
+    fn advance(&mut self, w: usize) -> Option<(usize, usize, usize)> {
+        // Control characters and zero-width marks print nothing.
+        if w == 0 {
+            return None;
+        }
+
+        // Wrap before a grapheme that does not fit, unless the row is empty.
+        if self.pending_wrap || (self.col > 0 && self.col + w > self.columns) {
+            self.col = 0;
+            self.pending_wrap = false;
+            self.row += 1;
+        }
+
+        let start = self.col;
+        self.col += w;
+
+        if self.col >= self.columns {
+            self.col = self.columns - 1;
+            self.pending_wrap = true;
+        }
+
+        Some((self.row, start, w))
+    }
+
+    #[cfg(not(feature = "policy"))]
+    fn width_of(&self, g: &str) -> usize {
+        get_display_width(g)
+    }
+
+    #[cfg(feature = "policy")]
+    fn width_of(&self, g: &str) -> usize {
+        self.policy.resolve_width(g)
+    }
+}
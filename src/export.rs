@@ -0,0 +1,230 @@
+//! HTML and SVG snapshots of terminal layouts.
+//!
+//! Screenshots of a terminal show what one font made of a layout; these
+//! exports show what runefix thinks it looks like. Every cell is drawn as a
+//! fixed-width box and a grapheme of width `n` spans exactly `n` boxes, so
+//! alignment bugs are visible in docs, bug reports, and golden tests no matter
+//! how the viewer's font measures the glyphs.
+//!
+//! - [`render_html`], [`CellGrid::to_html`] – A `<div>` of rows of `inline-block` spans
+//! - [`render_svg`], [`CellGrid::to_svg`] – A standalone `<svg>` with one outlined box per grapheme
+//!
+//! Text is laid out on a [`CellGrid`] just wide enough for its longest line,
+//! one row per line. ANSI escape sequences are skipped.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ansi::{Segment, segments};
+use crate::cursor::CursorSim;
+use crate::grid::{Cell, CellGrid};
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+
+/// Width of one SVG cell, in user units.
+const SVG_CELL_WIDTH: usize = 10;
+
+/// Height of one SVG cell, in user units.
+const SVG_CELL_HEIGHT: usize = 20;
+
+/// Renders `text` as an HTML snapshot, one row per line.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::render_html;
+///
+/// let html = render_html("a中");
+///
+/// assert!(html.contains(r#"<span class="w1">a</span><span class="w2">中</span>"#));
+/// ```
+pub fn render_html(text: &str) -> String {
+    text_grid(text, &CursorSim::new).to_html()
+}
+
+/// Same as [`render_html`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn render_html_with_policy(text: &str, policy: Option<&WidthPolicy>) -> String {
+    text_grid(text, &|columns| CursorSim::with_policy(columns, policy)).to_html()
+}
+
+/// Renders `text` as a standalone SVG snapshot, one row per line.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::render_svg;
+///
+/// let svg = render_svg("a中");
+///
+/// assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20""#));
+/// assert!(svg.contains(r#"<rect x="10" y="0" width="20" height="20"/>"#));
+/// ```
+pub fn render_svg(text: &str) -> String {
+    text_grid(text, &CursorSim::new).to_svg()
+}
+
+/// Same as [`render_svg`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn render_svg_with_policy(text: &str, policy: Option<&WidthPolicy>) -> String {
+    text_grid(text, &|columns| CursorSim::with_policy(columns, policy)).to_svg()
+}
+
+/// Lays out `text` on a grid as wide as its longest line.
+fn text_grid(text: &str, cursor: &dyn Fn(usize) -> CursorSim) -> CellGrid {
+    let lines: Vec<&str> = text.lines().collect();
+    let columns = lines
+        .iter()
+        .map(|line| line_extent(line, cursor(usize::MAX)))
+        .max()
+        .unwrap_or(0);
+
+    let mut grid = CellGrid::from_cursor(lines.len(), cursor(columns));
+    for (row, line) in lines.iter().enumerate() {
+        grid.write_at(row, 0, line);
+    }
+    grid
+}
+
+/// Returns the number of columns `line` prints into.
+///
+/// This is the rightmost cell reached, not where the cursor ends up: after
+/// `\r` or `\b`, later graphemes overwrite earlier ones instead.
+fn line_extent(line: &str, mut sim: CursorSim) -> usize {
+    let mut extent = 0;
+
+    for segment in segments(line) {
+        let placed = match segment {
+            Segment::Text(text) => text
+                .graphemes(true)
+                .filter_map(|g| sim.put(g))
+                .map(|(_, col, w)| col + w)
+                .max(),
+            Segment::Sized(sizing) => sim.put_sized(&sizing).map(|(_, col, w)| col + w),
+            Segment::Escape(_) => None,
+        };
+        extent = extent.max(placed.unwrap_or(0));
+    }
+
+    extent
+}
+
+impl CellGrid {
+    /// Renders the grid as an HTML snapshot.
+    ///
+    /// The output is a `<div class="runefix-grid">` with an embedded
+    /// `<style>` and one `<div>` per row. Each grapheme is a span of class
+    /// `w{n}` sized to `n` `ch` units; blank cells are `w1` spans holding a space.
+    pub fn to_html(&self) -> String {
+        let mut widths: Vec<usize> = self.leads().map(|(_, _, _, w)| w).collect();
+        widths.push(1);
+        widths.sort_unstable();
+        widths.dedup();
+
+        let mut out = String::from("<div class=\"runefix-grid\">\n<style>");
+        out.push_str(
+            ".runefix-grid{font-family:monospace;line-height:1.25;white-space:pre}\
+             .runefix-grid span{display:inline-block;text-align:center;overflow:visible}",
+        );
+        for w in widths {
+            let _ = write!(out, ".runefix-grid .w{w}{{width:{w}ch}}");
+        }
+        out.push_str("</style>\n");
+
+        for row in 0..self.rows() {
+            out.push_str("<div>");
+            for (_, text, w) in self.row_leads(row) {
+                let _ = write!(out, "<span class=\"w{w}\">");
+                push_escaped(&mut out, text);
+                out.push_str("</span>");
+            }
+            out.push_str("</div>\n");
+        }
+
+        out.push_str("</div>\n");
+        out
+    }
+
+    /// Renders the grid as a standalone SVG snapshot.
+    ///
+    /// Each cell is 10 × 20 user units. Every grapheme and blank cell is
+    /// outlined by a `<rect>` spanning its width, with the grapheme centered
+    /// in it as `<text>`.
+    pub fn to_svg(&self) -> String {
+        let (width, height) = (
+            self.columns() * SVG_CELL_WIDTH,
+            self.rows() * SVG_CELL_HEIGHT,
+        );
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"16\">"
+        );
+        let _ = writeln!(
+            out,
+            "<rect width=\"{width}\" height=\"{height}\" fill=\"#fff\"/>"
+        );
+
+        out.push_str("<g fill=\"none\" stroke=\"#ccc\">\n");
+        for (row, col, _, w) in self.leads() {
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{SVG_CELL_HEIGHT}\"/>",
+                col * SVG_CELL_WIDTH,
+                row * SVG_CELL_HEIGHT,
+                w * SVG_CELL_WIDTH,
+            );
+        }
+        out.push_str("</g>\n");
+
+        out.push_str("<g text-anchor=\"middle\" fill=\"#000\">\n");
+        for (row, col, text, w) in self.leads().filter(|&(.., text, _)| text != " ") {
+            let _ = write!(
+                out,
+                "<text x=\"{}\" y=\"{}\">",
+                col * SVG_CELL_WIDTH + w * SVG_CELL_WIDTH / 2,
+                row * SVG_CELL_HEIGHT + 15,
+            );
+            push_escaped(&mut out, text);
+            out.push_str("</text>\n");
+        }
+        out.push_str("</g>\n</svg>\n");
+        out
+    }
+
+    /// Yields `(row, col, text, width)` for every grapheme and blank cell.
+    fn leads(&self) -> impl Iterator<Item = (usize, usize, &str, usize)> + '_ {
+        (0..self.rows()).flat_map(move |row| {
+            self.row_leads(row)
+                .map(move |(col, text, w)| (row, col, text, w))
+        })
+    }
+
+    /// Yields `(col, text, width)` for every grapheme and blank cell of `row`.
+    fn row_leads(&self, row: usize) -> impl Iterator<Item = (usize, &str, usize)> + '_ {
+        (0..self.columns()).filter_map(move |col| match self.cell(row, col)? {
+            Cell::Blank => Some((col, " ", 1)),
+            Cell::Lead { grapheme, width } => Some((col, grapheme.as_str(), *width)),
+            Cell::Continuation => None,
+        })
+    }
+}
+
+/// Appends `s` with the HTML / XML special characters escaped.
+fn push_escaped(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
@@ -10,6 +10,9 @@
 //! - [`display_width`](crate::grapheme::display_width)
 //! - [`WidthPolicy`](crate::policy::WidthPolicy) for configurable strategies
 
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
 /// Extension trait for measuring the display width of runes, graphemes, and strings.
 ///
 /// This trait provides unified access to terminal display width calculations
@@ -47,6 +50,7 @@ pub trait RuneDisplayWidth {
     ///
     /// For `&str`, this segments the string using Unicode grapheme rules.
     /// For `char`, returns a single-item vector.
+    #[cfg(feature = "alloc")]
     fn display_widths(&self) -> Vec<usize>;
 
     /// Returns the total display width in terminal columns
@@ -65,6 +69,7 @@ impl RuneDisplayWidth for str {
         crate::grapheme::display_width(self)
     }
 
+    #[cfg(feature = "alloc")]
     fn display_widths(&self) -> Vec<usize> {
         crate::grapheme::display_widths(self)
     }
@@ -72,13 +77,14 @@ impl RuneDisplayWidth for str {
 
 impl RuneDisplayWidth for char {
     fn rune_width(&self) -> usize {
-        crate::width::get_display_width(&self.to_string())
+        crate::width::get_display_width(self.encode_utf8(&mut [0; 4]))
     }
 
     fn display_width(&self) -> usize {
         self.rune_width()
     }
 
+    #[cfg(feature = "alloc")]
     fn display_widths(&self) -> Vec<usize> {
         vec![self.rune_width()]
     }
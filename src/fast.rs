@@ -0,0 +1,145 @@
+//! Fast path for measuring whole strings made of narrow scripts.
+//!
+//! Full width resolution segments the input into grapheme clusters (UAX #29)
+//! and probes several lookup tables per cluster. For the most common input —
+//! printable ASCII, and Latin, Greek, or Cyrillic text — none of that is needed:
+//!
+//! - Printable ASCII (`0x20..=0x7E`) is always width `1` and always forms its
+//!   own cluster. It is skipped eight bytes at a time using word-sized
+//!   (SWAR) checks, which compilers readily auto-vectorize.
+//! - Characters listed in [`NARROW_RANGES`] never combine with their
+//!   neighbors and are absent from every width dataset, so each one resolves
+//!   to the policy's `fallback` width.
+//!
+//! At the first byte outside these sets, the fast path backs off by one
+//! character (which may start a cluster with what follows, e.g. `e` + U+0301)
+//! and hands over to the full grapheme-aware path. It resumes at the next
+//! cluster consisting of a single simple character, which is always a safe
+//! segmentation boundary. Results are identical to the full path by construction.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Code point ranges of narrow characters that always form a single-character
+/// grapheme cluster and are not part of any width dataset.
+///
+/// Derived from the Unicode 16.0 grapheme break properties: combining marks
+/// (`U+0300..=U+036F`, `U+0483..=U+0489`) are excluded.
+const NARROW_RANGES: &[(u32, u32)] = &[
+    (0x00A0, 0x02FF), // Latin-1 Supplement, Latin Extended-A/B, IPA, Spacing Modifiers
+    (0x0370, 0x0482), // Greek and Coptic, Cyrillic (before combining marks)
+    (0x048A, 0x052F), // Cyrillic (after combining marks), Cyrillic Supplement
+    (0x1E00, 0x1FFF), // Latin Extended Additional, Greek Extended
+];
+
+const LO: u64 = 0x0101_0101_0101_0101;
+const HI: u64 = 0x8080_8080_8080_8080;
+
+/// Returns the total display width of `s`, taking the fast path where possible.
+///
+/// * `fallback` - Width the active policy assigns to uncategorized graphemes
+/// * `width_of` - Full width resolution for a single grapheme cluster
+pub(crate) fn display_width_fast<F>(s: &str, fallback: usize, mut width_of: F) -> usize
+where
+    F: FnMut(&str) -> usize,
+{
+    let bytes = s.as_bytes();
+    let mut width = 0;
+    let mut pos = 0;
+
+    // Start byte and width of the last character consumed by the fast path.
+    let mut last = (0, 0);
+
+    while pos < bytes.len() {
+        let run = printable_ascii_prefix(&bytes[pos..]);
+
+        if run > 0 {
+            width += run;
+            pos += run;
+            last = (pos - 1, 1);
+            continue;
+        }
+
+        let c = s[pos..].chars().next().unwrap_or_default();
+
+        if is_narrow(c) {
+            width += fallback;
+            last = (pos, fallback);
+            pos += c.len_utf8();
+            continue;
+        }
+
+        // Back off one character, since it may form a cluster with `c`, then
+        // measure full clusters until a simple one marks a safe boundary.
+        let (start, w) = last;
+        width -= w;
+        pos = s.len();
+
+        for (i, g) in s[start..].grapheme_indices(true) {
+            if i > 0 && is_simple(g) {
+                pos = start + i;
+                break;
+            }
+
+            width += width_of(g);
+        }
+
+        last = (pos, 0);
+    }
+
+    width
+}
+
+/// Returns the length of the longest prefix consisting of printable ASCII bytes.
+fn printable_ascii_prefix(bytes: &[u8]) -> usize {
+    let mut chunks = bytes.chunks_exact(8);
+    let mut len = 0;
+
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+
+        if !is_printable_ascii_word(word) {
+            break;
+        }
+
+        len += 8;
+    }
+
+    len + bytes[len..]
+        .iter()
+        .take_while(|&&b| (0x20..0x7F).contains(&b))
+        .count()
+}
+
+/// Returns `true` if all eight bytes of `word` are in `0x20..=0x7E`.
+#[inline]
+fn is_printable_ascii_word(word: u64) -> bool {
+    // Any byte >= 0x80 (non-ASCII)
+    let high = word & HI;
+    // Any byte < 0x20 (control characters)
+    let low = word.wrapping_sub(LO * 0x20) & !word & HI;
+    // Any byte == 0x7F (DEL)
+    let del = word ^ (LO * 0x7F);
+    let del = del.wrapping_sub(LO) & !del & HI;
+
+    (high | low | del) == 0
+}
+
+/// Returns `true` if the grapheme `g` is a single printable ASCII or narrow character.
+fn is_simple(g: &str) -> bool {
+    let mut chars = g.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => (' '..='~').contains(&c) || is_narrow(c),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `c` is in [`NARROW_RANGES`].
+#[inline]
+fn is_narrow(c: char) -> bool {
+    let cp = c as u32;
+
+    NARROW_RANGES
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&cp))
+}
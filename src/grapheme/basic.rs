@@ -11,7 +11,15 @@
 //!
 //! See [`policy_ext`](crate::grapheme::policy_ext) for configurable width behavior.
 
-use crate::width::get_display_width;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+use crate::fast::display_width_fast;
+use crate::grapheme::iter::grapheme_widths_iter;
+#[cfg(feature = "alloc")]
+use crate::grapheme::iter::wrap_iter;
+use crate::width::{get_display_width, get_fallback_width};
+#[cfg(feature = "alloc")]
 use unicode_segmentation::UnicodeSegmentation;
 
 /// Returns all Unicode grapheme clusters in the input string, following UAX #29.
@@ -42,6 +50,7 @@ use unicode_segmentation::UnicodeSegmentation;
 /// let clusters = graphemes("Love👩‍❤️‍💋‍👨爱");
 /// assert_eq!(clusters, vec!["L", "o", "v", "e", "👩‍❤️‍💋‍👨", "爱"]);
 /// ```
+#[cfg(feature = "alloc")]
 pub fn graphemes(s: &str) -> Vec<&str> {
     UnicodeSegmentation::graphemes(s, true).collect()
 }
@@ -53,6 +62,9 @@ pub fn graphemes(s: &str) -> Vec<&str> {
 /// how much horizontal space the entire string occupies in a monospace terminal,
 /// accounting for wide characters such as CJK ideographs and emoji.
 ///
+/// Runs of printable ASCII, Latin, Greek, and Cyrillic text are measured on a
+/// fast path that skips segmentation; the result is identical either way.
+///
 /// # Arguments
 ///
 /// * `s` - The input string to measure
@@ -70,9 +82,7 @@ pub fn graphemes(s: &str) -> Vec<&str> {
 /// assert_eq!(width, 8); // 1 + 1 + 2 + 2 + 2
 /// ```
 pub fn display_width(s: &str) -> usize {
-    UnicodeSegmentation::graphemes(s, true)
-        .map(get_display_width)
-        .sum()
+    display_width_fast(s, get_fallback_width(), get_display_width)
 }
 
 /// Returns the display width (in columns) of each grapheme cluster in the input string.
@@ -98,10 +108,9 @@ pub fn display_width(s: &str) -> usize {
 /// let widths = display_widths("Hi，世界");
 /// assert_eq!(widths, vec![1, 1, 2, 2, 2]);
 /// ```
+#[cfg(feature = "alloc")]
 pub fn display_widths(s: &str) -> Vec<usize> {
-    UnicodeSegmentation::graphemes(s, true)
-        .map(get_display_width)
-        .collect()
+    grapheme_widths_iter(s).map(|(_, _, w, _)| w).collect()
 }
 
 /// Returns the display width of each grapheme cluster in the input string.
@@ -131,10 +140,9 @@ pub fn display_widths(s: &str) -> Vec<usize> {
 ///     vec![("H", 1), ("i", 1), ("，", 2), ("世", 2), ("界", 2)]
 /// );
 /// ```
+#[cfg(feature = "alloc")]
 pub fn grapheme_widths(s: &str) -> Vec<(&str, usize)> {
-    UnicodeSegmentation::graphemes(s, true)
-        .map(|g| (g, get_display_width(g)))
-        .collect()
+    grapheme_widths_iter(s).map(|(_, g, w, _)| (g, w)).collect()
 }
 
 /// Truncates a string by display width while preserving grapheme cluster boundaries.
@@ -163,19 +171,9 @@ pub fn grapheme_widths(s: &str) -> Vec<(&str, usize)> {
 /// assert_eq!(short, "Hi 👋");
 /// ```
 pub fn truncate_by_width(s: &str, max_width: usize) -> &str {
-    let mut total_width = 0;
-    let mut end_byte = 0;
-
-    for g in UnicodeSegmentation::graphemes(s, true) {
-        let w: usize = get_display_width(g);
-
-        if total_width + w > max_width {
-            break;
-        }
-
-        total_width += w;
-        end_byte += g.len(); // Byte offset to cut safely
-    }
+    let end_byte = grapheme_widths_iter(s)
+        .find(|&(_, _, w, column)| column + w > max_width)
+        .map_or(s.len(), |(i, _, _, _)| i); // Byte offset to cut safely
 
     &s[..end_byte]
 }
@@ -204,27 +202,53 @@ pub fn truncate_by_width(s: &str, max_width: usize) -> &str {
 /// let lines = split_by_width("Hello 👋 世界！", 5);
 /// assert_eq!(lines, vec!["Hello", " 👋 ", "世界", "！"]);
 /// ```
+#[cfg(feature = "alloc")]
 pub fn split_by_width(s: &str, max_width: usize) -> Vec<String> {
-    let mut result = Vec::new();
-    let mut current_line = String::new();
-    let mut current_width = 0;
+    wrap_iter(s, max_width).map(str::to_owned).collect()
+}
+
+/// Wraps each hard line (separated by `\n` or `\r\n`) independently at `max_width`.
+///
+/// Unlike [`split_by_width`], which treats `\n` as a zero-width grapheme, line
+/// breaks here reset the column. The line break itself is not included, and an
+/// empty hard line yields one empty line, as with [`str::split`].
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::wrap_lines;
+///
+/// let lines = wrap_lines("你好世界\n\nabc", 4);
+/// assert_eq!(lines, vec!["你好", "世界", "", "abc"]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn wrap_lines(s: &str, max_width: usize) -> Vec<&str> {
+    let mut lines = Vec::new();
 
-    for g in UnicodeSegmentation::graphemes(s, true) {
-        let w: usize = get_display_width(g);
+    for line in hard_lines(s) {
+        let before = lines.len();
+        lines.extend(wrap_iter(line, max_width));
 
-        if current_width + w > max_width && !current_line.is_empty() {
-            result.push(current_line.clone());
-            current_line.clear();
-            current_width = 0;
+        if lines.len() == before {
+            lines.push(line);
         }
-
-        current_line.push_str(g);
-        current_width += w;
     }
 
-    if !current_line.is_empty() {
-        result.push(current_line);
-    }
+    lines
+}
+
+/// Splits `s` at `\n` as [`str::split`] does, dropping the `\r` of each `\r\n`.
+#[cfg(feature = "alloc")]
+pub(crate) fn hard_lines(s: &str) -> impl Iterator<Item = &str> {
+    let mut pieces = s.split('\n').peekable();
+
+    core::iter::from_fn(move || {
+        let line = pieces.next()?;
 
-    result
+        // Only a `\r` followed by `\n` is part of the line break.
+        Some(match pieces.peek() {
+            Some(_) => line.strip_suffix('\r').unwrap_or(line),
+            None => line,
+        })
+    })
 }
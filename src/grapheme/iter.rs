@@ -0,0 +1,182 @@
+//! Lazy, allocation-free iterators over grapheme widths and wrapped lines.
+//!
+//! The `Vec`-returning functions in [`basic`](crate::grapheme::basic) and
+//! [`policy_ext`](crate::grapheme::policy_ext) are thin wrappers over the
+//! iterators defined here. Use the iterators directly on large inputs
+//! (multi-megabyte logs, documents) to avoid intermediate allocations:
+//!
+//! - [`grapheme_widths_iter`] – yields `(byte_offset, grapheme, width, column)`
+//! - [`wrap_iter`] – yields wrapped lines as borrowed `&str` slices
+
+use unicode_segmentation::{GraphemeIndices, UnicodeSegmentation};
+
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+#[cfg(not(feature = "policy"))]
+use crate::width::get_display_width;
+
+/// Iterator over grapheme clusters with their byte offset, width, and starting column.
+///
+/// Created by [`grapheme_widths_iter`].
+#[derive(Debug, Clone)]
+pub struct GraphemeWidths<'a> {
+    inner: GraphemeIndices<'a>,
+    column: usize,
+
+    #[cfg(feature = "policy")]
+    policy: WidthPolicy,
+}
+
+impl GraphemeWidths<'_> {
+    #[cfg(not(feature = "policy"))]
+    fn width_of(&self, g: &str) -> usize {
+        get_display_width(g)
+    }
+
+    #[cfg(feature = "policy")]
+    fn width_of(&self, g: &str) -> usize {
+        self.policy.resolve_width(g)
+    }
+}
+
+impl<'a> Iterator for GraphemeWidths<'a> {
+    /// `(byte_offset, grapheme, width, column)`
+    type Item = (usize, &'a str, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, g) = self.inner.next()?;
+        let w = self.width_of(g);
+        let column = self.column;
+
+        self.column += w;
+
+        Some((i, g, w, column))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator over lines wrapped by display width, borrowed from the input.
+///
+/// Created by [`wrap_iter`].
+#[derive(Debug, Clone)]
+pub struct WrapIter<'a> {
+    s: &'a str,
+    inner: GraphemeIndices<'a>,
+    pending: Option<(usize, &'a str)>,
+    max_width: usize,
+
+    #[cfg(feature = "policy")]
+    policy: WidthPolicy,
+}
+
+impl WrapIter<'_> {
+    #[cfg(not(feature = "policy"))]
+    fn width_of(&self, g: &str) -> usize {
+        get_display_width(g)
+    }
+
+    #[cfg(feature = "policy")]
+    fn width_of(&self, g: &str) -> usize {
+        self.policy.resolve_width(g)
+    }
+}
+
+impl<'a> Iterator for WrapIter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut start = None;
+        let mut end = 0;
+        let mut width = 0;
+
+        while let Some((i, g)) = self.pending.take().or_else(|| self.inner.next()) {
+            let w = self.width_of(g);
+
+            // A line always takes at least one grapheme, even if it is too wide.
+            if start.is_some() && width + w > self.max_width {
+                self.pending = Some((i, g));
+                break;
+            }
+
+            start.get_or_insert(i);
+            end = i + g.len();
+            width += w;
+        }
+
+        start.map(|start| &self.s[start..end])
+    }
+}
+
+/// Returns a lazy iterator of `(byte_offset, grapheme, width, column)` tuples.
+///
+/// `column` is the display column at which the grapheme starts, i.e. the sum of
+/// the widths of all preceding graphemes.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::grapheme_widths_iter;
+///
+/// let items: Vec<_> = grapheme_widths_iter("你a👇").collect();
+/// assert_eq!(items, vec![(0, "你", 2, 0), (3, "a", 1, 2), (4, "👇", 2, 3)]);
+/// ```
+pub fn grapheme_widths_iter(s: &str) -> GraphemeWidths<'_> {
+    GraphemeWidths {
+        inner: s.grapheme_indices(true),
+        column: 0,
+        #[cfg(feature = "policy")]
+        policy: WidthPolicy::terminal(),
+    }
+}
+
+/// Returns a lazy iterator of lines wrapped at `max_width`, borrowed from `s`.
+///
+/// Lines follow the same rules as [`split_by_width`](crate::split_by_width),
+/// which collects this iterator into owned strings.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::wrap_iter;
+///
+/// let lines: Vec<&str> = wrap_iter("Hello 👋 世界！", 5).collect();
+/// assert_eq!(lines, vec!["Hello", " 👋 ", "世界", "！"]);
+/// ```
+pub fn wrap_iter(s: &str, max_width: usize) -> WrapIter<'_> {
+    WrapIter {
+        s,
+        inner: s.grapheme_indices(true),
+        pending: None,
+        max_width,
+        #[cfg(feature = "policy")]
+        policy: WidthPolicy::terminal(),
+    }
+}
+
+/// Same as [`grapheme_widths_iter`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn grapheme_widths_iter_with_policy<'a>(
+    s: &'a str,
+    policy: Option<&WidthPolicy>,
+) -> GraphemeWidths<'a> {
+    GraphemeWidths {
+        policy: policy.cloned().unwrap_or_else(WidthPolicy::terminal),
+        ..grapheme_widths_iter(s)
+    }
+}
+
+/// Same as [`wrap_iter`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn wrap_iter_with_policy<'a>(
+    s: &'a str,
+    max_width: usize,
+    policy: Option<&WidthPolicy>,
+) -> WrapIter<'a> {
+    WrapIter {
+        policy: policy.cloned().unwrap_or_else(WidthPolicy::terminal),
+        ..wrap_iter(s, max_width)
+    }
+}
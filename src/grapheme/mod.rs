@@ -1,5 +1,7 @@
 pub mod basic;
+pub mod iter;
 pub use basic::*;
+pub use iter::*;
 
 #[cfg(feature = "policy")]
 pub mod policy_ext;
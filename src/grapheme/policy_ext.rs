@@ -12,30 +12,41 @@
 //!
 //! All functions in this module are gated behind the `policy` feature flag.
 
-use crate::{policy::WidthPolicy, width::get_display_width_with_policy};
-use unicode_segmentation::UnicodeSegmentation;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+use crate::fast::display_width_fast;
+#[cfg(feature = "alloc")]
+use crate::grapheme::basic::hard_lines;
+use crate::grapheme::iter::grapheme_widths_iter_with_policy;
+#[cfg(feature = "alloc")]
+use crate::grapheme::iter::wrap_iter_with_policy;
+use crate::policy::WidthPolicy;
 
 /// Same as [`display_width`](crate::display_width), but applies the given [`WidthPolicy`] strategy.
 pub fn display_width_with_policy(s: &str, policy: Option<&WidthPolicy>) -> usize {
-    UnicodeSegmentation::graphemes(s, true)
-        .map(|g| get_display_width_with_policy(g, policy))
-        .sum()
+    let terminal = WidthPolicy::terminal();
+    let policy = policy.unwrap_or(&terminal);
+
+    display_width_fast(s, policy.fallback, |g| policy.resolve_width(g))
 }
 
 /// Same as [`display_widths`](crate::display_widths), but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "alloc")]
 pub fn display_widths_with_policy(s: &str, policy: Option<&WidthPolicy>) -> Vec<usize> {
-    UnicodeSegmentation::graphemes(s, true)
-        .map(|g| get_display_width_with_policy(g, policy))
+    grapheme_widths_iter_with_policy(s, policy)
+        .map(|(_, _, w, _)| w)
         .collect()
 }
 
 /// Same as [`grapheme_widths`](crate::grapheme_widths), but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "alloc")]
 pub fn grapheme_widths_with_policy<'a>(
     s: &'a str,
     policy: Option<&WidthPolicy>,
 ) -> Vec<(&'a str, usize)> {
-    UnicodeSegmentation::graphemes(s, true)
-        .map(|g| (g, get_display_width_with_policy(g, policy)))
+    grapheme_widths_iter_with_policy(s, policy)
+        .map(|(_, g, w, _)| (g, w))
         .collect()
 }
 
@@ -45,49 +56,42 @@ pub fn truncate_by_width_with_policy<'a>(
     max_width: usize,
     policy: Option<&WidthPolicy>,
 ) -> &'a str {
-    let mut total_width = 0;
-    let mut end_byte = 0;
-
-    for g in UnicodeSegmentation::graphemes(s, true) {
-        let w: usize = get_display_width_with_policy(g, policy);
-
-        if total_width + w > max_width {
-            break;
-        }
-
-        total_width += w;
-        end_byte += g.len();
-    }
+    let end_byte = grapheme_widths_iter_with_policy(s, policy)
+        .find(|&(_, _, w, column)| column + w > max_width)
+        .map_or(s.len(), |(i, _, _, _)| i);
 
     &s[..end_byte]
 }
 
 /// Same as [`split_by_width`](crate::split_by_width), but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "alloc")]
 pub fn split_by_width_with_policy(
     s: &str,
     max_width: usize,
     policy: Option<&WidthPolicy>,
 ) -> Vec<String> {
-    let mut result = Vec::new();
-    let mut current_line = String::new();
-    let mut current_width = 0;
-
-    for g in UnicodeSegmentation::graphemes(s, true) {
-        let w: usize = get_display_width_with_policy(g, policy);
+    wrap_iter_with_policy(s, max_width, policy)
+        .map(str::to_owned)
+        .collect()
+}
 
-        if current_width + w > max_width && !current_line.is_empty() {
-            result.push(current_line.clone());
-            current_line.clear();
-            current_width = 0;
-        }
+/// Same as [`wrap_lines`](crate::wrap_lines), but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "alloc")]
+pub fn wrap_lines_with_policy<'a>(
+    s: &'a str,
+    max_width: usize,
+    policy: Option<&WidthPolicy>,
+) -> Vec<&'a str> {
+    let mut lines = Vec::new();
 
-        current_line.push_str(g);
-        current_width += w;
-    }
+    for line in hard_lines(s) {
+        let before = lines.len();
+        lines.extend(wrap_iter_with_policy(line, max_width, policy));
 
-    if !current_line.is_empty() {
-        result.push(current_line);
+        if lines.len() == before {
+            lines.push(line);
+        }
     }
 
-    result
+    lines
 }
@@ -0,0 +1,285 @@
+//! In-memory terminal screen for testing TUI output.
+//!
+//! [`CellGrid`] stores a fixed number of rows and columns of [`Cell`]s. Text is
+//! written through a cursor that moves like a terminal's (see [`CursorSim`]),
+//! so rendered frames can be asserted on or diffed without a real terminal:
+//!
+//! - A grapheme of width `n` occupies a [`Cell::Lead`] followed by `n - 1`
+//!   [`Cell::Continuation`]s, and is stored intact (ZWJ sequences, combining marks).
+//! - Overwriting any cell of a wide grapheme blanks the rest of it.
+//! - Printing past the bottom row scrolls the grid up.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ansi::{Segment, segments};
+use crate::cursor::CursorSim;
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+
+/// A single cell of a [`CellGrid`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// A cell with nothing printed in it, rendered as a space.
+    #[default]
+    Blank,
+
+    /// The first cell of a grapheme, holding the whole cluster and its width.
+    Lead {
+        /// The grapheme cluster.
+        grapheme: String,
+
+        /// The number of cells the grapheme occupies.
+        width: usize,
+    },
+
+    /// A cell covered by the wide grapheme to its left.
+    Continuation,
+}
+
+impl Cell {
+    /// Returns the text this cell renders: a space, its grapheme, or nothing.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Cell::Blank => " ",
+            Cell::Lead { grapheme, .. } => grapheme,
+            Cell::Continuation => "",
+        }
+    }
+
+    /// Returns `true` for a [`Cell::Continuation`].
+    pub fn is_continuation(&self) -> bool {
+        matches!(self, Cell::Continuation)
+    }
+}
+
+/// A `rows` × `columns` grid of cells with a terminal-style cursor.
+///
+/// Control characters and auto-wrapping behave as documented on
+/// [`CursorSim`]. ANSI escape sequences are skipped; colors and cursor
+/// movement sequences are not interpreted. The text of a kitty text-sizing
+/// sequence is stored in one lead cell spanning the cells it declares.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{Cell, CellGrid};
+///
+/// let mut grid = CellGrid::new(2, 6);
+/// grid.print("ab中文\r\n🇯🇵!");
+///
+/// // Overwriting the right half of `中` blanks its left half.
+/// grid.write_at(0, 3, "x");
+///
+/// assert_eq!(grid.lines(), ["ab x文", "🇯🇵!   "]);
+/// assert_eq!(grid.cell(1, 1), Some(&Cell::Continuation));
+/// assert_eq!(grid.cursor(), (0, 4));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellGrid {
+    rows: usize,
+    columns: usize,
+
+    /// Row-major cells, `rows * columns` long.
+    cells: Vec<Cell>,
+
+    cursor: CursorSim,
+}
+
+impl CellGrid {
+    /// Creates a blank grid with the cursor at the top-left corner.
+    ///
+    /// Dimensions of `0` are treated as `1`.
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Self::from_cursor(rows, CursorSim::new(columns))
+    }
+
+    /// Creates a blank grid using the given [`WidthPolicy`] strategy.
+    #[cfg(feature = "policy")]
+    pub fn with_policy(rows: usize, columns: usize, policy: Option<&WidthPolicy>) -> Self {
+        Self::from_cursor(rows, CursorSim::with_policy(columns, policy))
+    }
+
+    /// Creates a blank grid whose columns and widths come from `cursor`.
+    pub(crate) fn from_cursor(rows: usize, cursor: CursorSim) -> Self {
+        let rows = rows.max(1);
+        let columns = cursor.columns();
+
+        Self {
+            rows,
+            columns,
+            cells: alloc::vec![Cell::Blank; rows * columns],
+            cursor,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the cell at `(row, col)`, or `None` if it is outside the grid.
+    pub fn cell(&self, row: usize, col: usize) -> Option<&Cell> {
+        match row < self.rows && col < self.columns {
+            true => self.cells.get(row * self.columns + col),
+            false => None,
+        }
+    }
+
+    /// Returns the cursor position as `(row, column)`.
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor.position()
+    }
+
+    /// Moves the cursor to `(row, col)`, clamped to the grid.
+    pub fn move_to(&mut self, row: usize, col: usize) {
+        self.cursor.set_position(row.min(self.rows - 1), col);
+    }
+
+    /// Prints `s` at the cursor, wrapping at the right edge and scrolling at the bottom.
+    pub fn print(&mut self, s: &str) {
+        for segment in segments(s) {
+            match segment {
+                Segment::Text(text) => {
+                    for g in text.graphemes(true) {
+                        let placed = self.cursor.put(g);
+                        self.place(g, placed);
+                    }
+                }
+                Segment::Sized(sizing) => {
+                    let placed = self.cursor.put_sized(&sizing);
+                    self.place(sizing.text, placed);
+                }
+                Segment::Escape(_) => {}
+            }
+        }
+    }
+
+    /// Scrolls if the cursor moved past the bottom, then stores `text` where it was printed.
+    fn place(&mut self, text: &str, placed: Option<(usize, usize, usize)>) {
+        if self.cursor.row() >= self.rows {
+            self.scroll_up(1);
+            self.cursor.set_row(self.rows - 1);
+        }
+
+        if let Some((_, col, width)) = placed {
+            self.put_cell(self.cursor.row(), col, text, width);
+        }
+    }
+
+    /// Moves the cursor to `(row, col)` and prints `s`.
+    pub fn write_at(&mut self, row: usize, col: usize, s: &str) {
+        self.move_to(row, col);
+        self.print(s);
+    }
+
+    /// Blanks every cell and moves the cursor to the top-left corner.
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::Blank);
+        self.cursor.set_position(0, 0);
+    }
+
+    /// Blanks every cell of `row`. The cursor does not move.
+    pub fn clear_row(&mut self, row: usize) {
+        if row < self.rows {
+            self.row_mut(row).fill(Cell::Blank);
+        }
+    }
+
+    /// Scrolls the contents up by `n` rows, adding blank rows at the bottom.
+    ///
+    /// The cursor does not move.
+    pub fn scroll_up(&mut self, n: usize) {
+        let n = n.min(self.rows) * self.columns;
+        self.cells.drain(..n);
+        self.cells.resize(self.rows * self.columns, Cell::Blank);
+    }
+
+    /// Scrolls the contents down by `n` rows, adding blank rows at the top.
+    ///
+    /// The cursor does not move.
+    pub fn scroll_down(&mut self, n: usize) {
+        let n = n.min(self.rows) * self.columns;
+        self.cells.truncate(self.rows * self.columns - n);
+        self.cells
+            .splice(0..0, core::iter::repeat_n(Cell::Blank, n));
+    }
+
+    /// Renders `row` as a string, with blank cells as spaces.
+    ///
+    /// Returns `None` if `row` is outside the grid.
+    pub fn line(&self, row: usize) -> Option<String> {
+        (row < self.rows).then(|| self.row(row).iter().map(Cell::as_str).collect())
+    }
+
+    /// Renders every row as a string, with blank cells as spaces.
+    pub fn lines(&self) -> Vec<String> {
+        self.cells
+            .chunks(self.columns)
+            .map(|row| row.iter().map(Cell::as_str).collect())
+            .collect()
+    }
+
+    fn row(&self, row: usize) -> &[Cell] {
+        &self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    fn row_mut(&mut self, row: usize) -> &mut [Cell] {
+        &mut self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    /// Stores `g` at `(row, col)`, blanking any grapheme it partly covers.
+    fn put_cell(&mut self, row: usize, col: usize, g: &str, width: usize) {
+        // A grapheme wider than the grid is clipped at the right edge.
+        let end = (col + width).min(self.columns);
+        let cells = self.row_mut(row);
+
+        for i in col..end {
+            erase(cells, i);
+        }
+
+        cells[col] = Cell::Lead {
+            grapheme: g.to_string(),
+            width,
+        };
+        cells[col + 1..end].fill(Cell::Continuation);
+    }
+}
+
+/// Blanks the whole grapheme covering cell `i`.
+fn erase(cells: &mut [Cell], i: usize) {
+    let lead = (0..=i)
+        .rev()
+        .find(|&j| !cells[j].is_continuation())
+        .unwrap_or(0);
+
+    let end = match &cells[lead] {
+        Cell::Lead { width, .. } => (lead + width).min(cells.len()),
+        _ => lead + 1,
+    };
+
+    cells[lead..end.max(i + 1)].fill(Cell::Blank);
+}
+
+impl Display for CellGrid {
+    /// Writes the rows separated by `\n`, with blank cells as spaces.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.cells.chunks(self.columns).enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            for cell in row {
+                f.write_str(cell.as_str())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+//! Kitty text-sizing protocol (OSC 66) output.
+//!
+//! Terminals disagree on the width of emoji, flags, ZWJ sequences, and East
+//! Asian Ambiguous characters, and every disagreement shifts the rest of the
+//! line. Kitty's text-sizing protocol lets the application state the width:
+//!
+//! ```text
+//! ESC ] 66 ; w=<cells> ; <text> ESC \
+//! ```
+//!
+//! [`encode_text_sizing`] wraps graphemes in such sequences carrying the width
+//! runefix computed, so a supporting terminal draws them in exactly that many
+//! cells. The ANSI-aware functions ([`display_width_ansi`](crate::display_width_ansi),
+//! [`strip_ansi`](crate::strip_ansi), [`truncate_ansi`](crate::truncate_ansi), …)
+//! understand the sequences, so encoded strings measure the same as the input.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt::Write;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ansi::{Segment, segments};
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+#[cfg(feature = "policy")]
+use crate::width::get_display_width_with_policy;
+use crate::width::{get_display_width, is_contested};
+
+/// The widths the protocol can express (`w=1` … `w=7`).
+const MAX_CELLS: usize = 7;
+
+/// Which graphemes [`encode_text_sizing`] wraps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextSizingMode {
+    /// Only graphemes whose width terminals disagree on: emoji, flags, ZWJ and
+    /// variation selector sequences, and East Asian Ambiguous characters.
+    #[default]
+    Contested,
+
+    /// Every printable grapheme.
+    All,
+}
+
+/// Wraps graphemes in kitty text-sizing sequences (OSC 66) declaring their width.
+///
+/// Control characters, zero-width graphemes, and graphemes wider than 7
+/// columns are left as they are, as are existing escape sequences, so the
+/// encoding is idempotent. Returns the input borrowed when nothing is wrapped.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{TextSizingMode, display_width_ansi, encode_text_sizing, strip_ansi};
+///
+/// let s = "ok 👍🏽 ±1";
+/// let encoded = encode_text_sizing(s, TextSizingMode::Contested);
+///
+/// assert_eq!(encoded, "ok \x1b]66;w=2;👍🏽\x1b\\ \x1b]66;w=1;±\x1b\\1");
+/// assert_eq!(display_width_ansi(&encoded), 8);
+/// assert_eq!(strip_ansi(&encoded), s);
+/// ```
+pub fn encode_text_sizing(s: &str, mode: TextSizingMode) -> Cow<'_, str> {
+    encode_text_sizing_impl(s, mode, &get_display_width)
+}
+
+/// Same as [`encode_text_sizing`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn encode_text_sizing_with_policy<'a>(
+    s: &'a str,
+    mode: TextSizingMode,
+    policy: Option<&WidthPolicy>,
+) -> Cow<'a, str> {
+    encode_text_sizing_impl(s, mode, &|g| get_display_width_with_policy(g, policy))
+}
+
+fn encode_text_sizing_impl<'a>(
+    s: &'a str,
+    mode: TextSizingMode,
+    width_of: &dyn Fn(&str) -> usize,
+) -> Cow<'a, str> {
+    let sized = |g: &str| {
+        let w = width_of(g);
+        let wanted = match mode {
+            TextSizingMode::Contested => is_contested(g),
+            TextSizingMode::All => true,
+        };
+        (wanted && (1..=MAX_CELLS).contains(&w)).then_some(w)
+    };
+
+    let any = segments(s).any(|segment| match segment {
+        Segment::Text(text) => text.graphemes(true).any(|g| sized(g).is_some()),
+        _ => false,
+    });
+
+    if !any {
+        return Cow::Borrowed(s);
+    }
+
+    let mut out = String::with_capacity(s.len() * 2);
+
+    for segment in segments(s) {
+        let Segment::Text(text) = segment else {
+            out.push_str(segment.as_str());
+            continue;
+        };
+
+        for g in text.graphemes(true) {
+            match sized(g) {
+                Some(w) => {
+                    let _ = write!(out, "\x1b]66;w={w};{g}\x1b\\");
+                }
+                None => out.push_str(g),
+            }
+        }
+    }
+
+    Cow::Owned(out)
+}
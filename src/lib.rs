@@ -25,10 +25,45 @@
 //! - [`display_width`] – Total width of a string (grapheme-aware, terminal-style)
 //! - [`display_widths`] – Widths of each grapheme cluster (`Vec<usize>`)
 //! - [`grapheme_widths`] – Widths with original clusters (`Vec<(&str, usize)>`)
+//! - [`grapheme_widths_iter`] – Lazy `(byte_offset, grapheme, width, column)` iterator
+//! - [`display_width_ansi`] – Width ignoring ANSI escape sequences (CSI, OSC, DCS, …)
+//! - [`strip_ansi`] – Removes ANSI escape sequences
+//! - [`prompt_width`] – Width of a bash / zsh / readline prompt with non-printing markers
+//! - [`mark_prompt_escapes`] – Encloses raw escape codes in a shell's non-printing markers
+//!
+//! 🗂️ **Measured Strings**
+//! - [`MeasuredStr`] – Segments once; answers width in `O(1)` and layout queries by binary search
+//! - [`WidthSummary`] – Associative per-chunk summary for ropes (width, lines, last-line width)
+//! - [`WidthCache`] – Thread-safe LRU of string widths, backed by a per-thread grapheme cache, with hit / miss [`CacheStats`]
+//! - [`StreamMeasurer`] – Measures arbitrary `&[u8]` chunks (PTY output, tailed logs) incrementally
+//! - [`CursorSim`] – Predicts the cursor position after printing, including pending wrap at the right margin
+//! - [`CellGrid`] – In-memory terminal screen with wide-grapheme continuation cells, for testing TUI output
+//! - [`render_html`], [`render_svg`] – HTML / SVG snapshots with one fixed-width box per cell (also on [`CellGrid`])
+//!
+//! 🎯 **Position API**
+//! - [`column_of_byte`] – Display column of a byte offset
+//! - [`byte_at_column`] – Byte offset at a display column (hit-testing, with [`Bias`])
+//! - [`columns_of_byte_range`] – Column span of a byte range (highlighting)
 //!
 //! 📐 **Layout API**
 //! - [`truncate_by_width`] – Truncates text by width without splitting graphemes
 //! - [`split_by_width`] – Wraps a string into lines based on terminal width
+//! - [`wrap_iter`] – Lazy line wrapping yielding borrowed `&str` slices
+//! - [`wrap_lines`] – Wraps each hard line (`\n`-separated) independently
+//! - [`truncate_with`] – Truncates with an ellipsis at the end, start, or middle
+//! - [`truncate_path`] – Truncates a path by eliding directories before the file name
+//! - [`pad_left`], [`pad_right`], [`pad_center`] – Pads text to a width with any fill
+//! - [`fit`] – Truncates and pads text to exactly the given width
+//! - [`columns`] – `Display` adapter making `{:>12}` / `{:.8}` count columns
+//! - [`slice_by_columns`] – Extracts the text visible in a column range (horizontal scrolling)
+//! - [`truncate_ansi`], [`wrap_ansi`] – Truncate / wrap colored text, keeping SGR styles and OSC 8 links balanced
+//! - [`encode_text_sizing`] – Declares contested grapheme widths to the terminal with kitty's OSC 66
+//! - [`render_aligned`] – Pins the column after each contested grapheme with a cursor move (CHA)
+//! - [`WrapWriter`], [`TruncateWriter`] – `io::Write` adapters that wrap or clip lines as bytes are written
+//!
+//! 🧮 **Compile-time API**
+//! - [`char_width`] – `const fn` width of a `char`
+//! - [`width!`] – Width of a string literal as a constant (array lengths, layout constants)
 //!
 //! 🍭 **Ergonomic Extensions**
 //! - [`RuneDisplayWidth`] – Trait for:
@@ -54,21 +89,127 @@
 //! - [🧪 docs.rs Documentation](https://docs.rs/runefix-core)
 //!
 //! > **Note:** Enable the `policy` feature to use configurable width strategies
-//! > such as `terminal()`, `markdown()`, or `compact()`.
+//! > such as `terminal()`, `markdown()`, or `compact()`, and the `rayon` feature
+//! > for parallel batch APIs (`display_widths_par`, `max_column_widths_par`,
+//! > `wrap_lines_par`).
+//!
+//! ## `no_std` support
+//!
+//! The crate is `#![no_std]`. Width tables are static data, so width resolution,
+//! truncation, the lazy iterators, and the position API work without an allocator.
+//!
+//! - `alloc` – APIs returning `Vec`, `String`, or `Cow` (e.g. [`graphemes`], [`pad_left`])
+//! - `std` (default, implies `alloc`) – [`truncate_path`], [`WidthCache`], and the
+//!   `io::Write` adapters
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 // ───── Public APIs ─────────────────────────────────────────────
 
 // Atom-based segmentation for layout units (runefix-specific)
+#[cfg(feature = "alloc")]
 pub use atom::atoms;
 
 // Grapheme-based core processing functions (always available)
-pub use grapheme::{
-    display_width, display_widths, grapheme_widths, graphemes, split_by_width, truncate_by_width,
-};
+pub use grapheme::{display_width, truncate_by_width};
+
+// Grapheme-based functions returning owned collections
+#[cfg(feature = "alloc")]
+pub use grapheme::{display_widths, grapheme_widths, graphemes, split_by_width, wrap_lines};
+
+// Lazy, allocation-free iterators behind the grapheme functions
+pub use grapheme::{GraphemeWidths, WrapIter, grapheme_widths_iter, wrap_iter};
+
+// Ellipsis-aware truncation with configurable placement
+#[cfg(feature = "alloc")]
+pub use truncate::{TruncateOptions, TruncatePosition, Truncated, truncate_with};
+
+// Path-aware truncation preserving file names
+#[cfg(feature = "std")]
+pub use path::truncate_path;
+
+// Width-aware padding and alignment
+#[cfg(feature = "alloc")]
+pub use pad::{Align, fit, pad_center, pad_left, pad_right};
+
+// Column-correct std formatting adapter
+#[cfg(feature = "alloc")]
+pub use columns::{Columns, columns};
+
+// Column-range slicing for horizontal scrolling
+pub use slice::{ColumnSlice, slice_by_columns};
+
+// Byte offset ↔ display column mapping
+pub use position::{Bias, byte_at_column, column_of_byte, columns_of_byte_range};
+
+// Pre-measured string with a prefix-width index
+#[cfg(feature = "alloc")]
+pub use measured::MeasuredStr;
+
+// Associative width summaries for rope-based editors
+#[cfg(feature = "alloc")]
+pub use summary::WidthSummary;
+
+// Memoized width measurement
+#[cfg(feature = "std")]
+pub use cache::{CacheStats, WidthCache};
+
+// Incremental measurement of byte streams
+#[cfg(feature = "alloc")]
+pub use stream::StreamMeasurer;
+
+// Terminal cursor position prediction
+pub use cursor::CursorSim;
+
+// In-memory terminal cell grid
+#[cfg(feature = "alloc")]
+pub use grid::{Cell, CellGrid};
+
+// HTML and SVG snapshots of text and cell grids
+#[cfg(feature = "alloc")]
+pub use export::{render_html, render_svg};
+
+// Width-enforcing `io::Write` adapters
+#[cfg(feature = "std")]
+pub use writer::{IntoInnerError, TruncateWriter, WrapWriter};
+
+// ANSI escape sequence aware measurement
+pub use ansi::display_width_ansi;
+
+// Removal of ANSI escape sequences
+#[cfg(feature = "alloc")]
+pub use ansi::strip_ansi;
+
+// Style-preserving truncation and wrapping of ANSI-colored text
+#[cfg(feature = "alloc")]
+pub use styled::{truncate_ansi, wrap_ansi};
+
+// Kitty text-sizing protocol (OSC 66) output
+#[cfg(feature = "alloc")]
+pub use kitty::{TextSizingMode, encode_text_sizing};
+
+// Alignment-forcing output via absolute cursor positioning
+#[cfg(feature = "alloc")]
+pub use aligned::render_aligned;
+
+// Shell prompt measurement with non-printing markers
+pub use prompt::{Shell, prompt_width};
+
+// Insertion of non-printing markers around escape sequences
+#[cfg(feature = "alloc")]
+pub use prompt::mark_prompt_escapes;
 
 // Unicode-aware trait extensions for `char` and `str`
 pub use ext::RuneDisplayWidth;
 
+// Compile-time width computation (see also the `width!` macro)
+pub use const_width::{char_width, display_width_const};
+
 // Unicode data version used internally
 pub use consts::UNICODE_VERSION;
 
@@ -79,26 +220,139 @@ pub use consts::UNICODE_VERSION;
 pub use policy::WidthPolicy;
 
 // Ergonomic wrapper for applying a WidthPolicy to strings
-#[cfg(feature = "policy")]
+#[cfg(all(feature = "policy", feature = "alloc"))]
 pub use with_policy::WithPolicy;
 
 // Policy-aware versions of grapheme layout functions
 #[cfg(feature = "policy")]
+pub use crate::grapheme::policy_ext::{display_width_with_policy, truncate_by_width_with_policy};
+
+// Policy-aware versions of functions returning owned collections
+#[cfg(all(feature = "policy", feature = "alloc"))]
 pub use crate::grapheme::policy_ext::{
-    display_width_with_policy, display_widths_with_policy, grapheme_widths_with_policy,
-    split_by_width_with_policy, truncate_by_width_with_policy,
+    display_widths_with_policy, grapheme_widths_with_policy, split_by_width_with_policy,
+    wrap_lines_with_policy,
+};
+
+// Policy-aware version of ANSI-aware measurement
+#[cfg(feature = "policy")]
+pub use ansi::display_width_ansi_with_policy;
+
+// Policy-aware versions of style-preserving truncation and wrapping
+#[cfg(all(feature = "policy", feature = "alloc"))]
+pub use styled::{truncate_ansi_with_policy, wrap_ansi_with_policy};
+
+// Policy-aware version of text-sizing output
+#[cfg(all(feature = "policy", feature = "alloc"))]
+pub use kitty::encode_text_sizing_with_policy;
+
+// Policy-aware version of alignment-forcing output
+#[cfg(all(feature = "policy", feature = "alloc"))]
+pub use aligned::render_aligned_with_policy;
+
+// Policy-aware version of prompt measurement
+#[cfg(feature = "policy")]
+pub use prompt::prompt_width_with_policy;
+
+// Policy-aware versions of HTML and SVG snapshots
+#[cfg(all(feature = "policy", feature = "alloc"))]
+pub use export::{render_html_with_policy, render_svg_with_policy};
+
+// Policy-aware versions of the lazy iterators
+#[cfg(feature = "policy")]
+pub use crate::grapheme::iter::{grapheme_widths_iter_with_policy, wrap_iter_with_policy};
+
+// Policy-aware version of ellipsis truncation
+#[cfg(all(feature = "policy", feature = "alloc"))]
+pub use truncate::truncate_with_policy;
+
+// Policy-aware version of path truncation
+#[cfg(all(feature = "policy", feature = "std"))]
+pub use path::truncate_path_with_policy;
+
+// Policy-aware versions of padding and alignment
+#[cfg(all(feature = "policy", feature = "alloc"))]
+pub use pad::{
+    fit_with_policy, pad_center_with_policy, pad_left_with_policy, pad_right_with_policy,
+};
+
+// Policy-aware column formatting adapter
+#[cfg(all(feature = "policy", feature = "alloc"))]
+pub use columns::columns_with_policy;
+
+// Policy-aware version of column-range slicing
+#[cfg(feature = "policy")]
+pub use slice::slice_by_columns_with_policy;
+
+// Policy-aware versions of byte offset ↔ column mapping
+#[cfg(feature = "policy")]
+pub use position::{
+    byte_at_column_with_policy, column_of_byte_with_policy, columns_of_byte_range_with_policy,
+};
+
+// ───── Optional: Feature-gated APIs (requires `rayon`) ──────────
+
+// Parallel batch measurement
+#[cfg(feature = "rayon")]
+pub use par::{display_widths_par, max_column_widths_par, wrap_lines_par};
+
+// Policy-aware versions of parallel batch measurement
+#[cfg(all(feature = "rayon", feature = "policy"))]
+pub use par::{
+    display_widths_par_with_policy, max_column_widths_par_with_policy, wrap_lines_par_with_policy,
 };
 
 // ───── Internal Modules (implementation details) ───────────────
 
-mod atom;
+mod ansi;
+mod const_width;
 mod consts;
+mod cursor;
 mod ext;
+mod fast;
 mod grapheme;
+mod position;
+mod prompt;
 mod rules;
+mod slice;
 mod width;
 
+#[cfg(feature = "alloc")]
+mod aligned;
+#[cfg(feature = "alloc")]
+mod atom;
+#[cfg(feature = "alloc")]
+mod columns;
+#[cfg(feature = "alloc")]
+mod export;
+#[cfg(feature = "alloc")]
+mod grid;
+#[cfg(feature = "alloc")]
+mod kitty;
+#[cfg(feature = "alloc")]
+mod measured;
+#[cfg(feature = "alloc")]
+mod pad;
+#[cfg(feature = "alloc")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod styled;
+#[cfg(feature = "alloc")]
+mod summary;
+#[cfg(feature = "alloc")]
+mod truncate;
+
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "std")]
+mod writer;
+
 #[cfg(feature = "policy")]
 mod policy;
-#[cfg(feature = "policy")]
+#[cfg(all(feature = "policy", feature = "alloc"))]
 mod with_policy;
+
+#[cfg(feature = "rayon")]
+mod par;
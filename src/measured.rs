@@ -0,0 +1,219 @@
+//! Pre-measured strings with a prefix-width index.
+//!
+//! Renderers often ask many questions about the same text — its width, where
+//! to truncate it, which columns a match covers — every frame. Each free
+//! function re-segments the input; [`MeasuredStr`] segments once and keeps the
+//! grapheme boundaries together with their cumulative widths, so that:
+//!
+//! - [`width`](MeasuredStr::width) is `O(1)`
+//! - truncation, column slicing, byte ↔ column mapping, and wrapping are
+//!   `O(log n)` binary searches per query (or per line)
+//!
+//! Results are identical to the corresponding free functions.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::grapheme::grapheme_widths_iter;
+use crate::grapheme::iter::GraphemeWidths;
+#[cfg(feature = "policy")]
+use crate::grapheme::iter::grapheme_widths_iter_with_policy;
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::position::Bias;
+use crate::slice::ColumnSlice;
+
+/// A borrowed string with an index of grapheme boundaries and cumulative widths.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{Bias, MeasuredStr};
+///
+/// let m = MeasuredStr::new("你a1👇");
+/// assert_eq!(m.width(), 6);
+/// assert_eq!(m.truncate(3), "你a");
+/// assert_eq!(m.byte_at_column(5, Bias::Left), 5);
+/// assert_eq!(m.wrap(4).collect::<Vec<_>>(), vec!["你a1", "👇"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeasuredStr<'a> {
+    s: &'a str,
+
+    /// Byte offset of each grapheme start, followed by `s.len()`.
+    bytes: Vec<usize>,
+
+    /// Display column of each entry in `bytes` (prefix sums of widths).
+    columns: Vec<usize>,
+}
+
+impl<'a> MeasuredStr<'a> {
+    /// Segments and measures `s` using the default terminal strategy.
+    pub fn new(s: &'a str) -> Self {
+        Self::from_iter(s, grapheme_widths_iter(s))
+    }
+
+    /// Segments and measures `s` using the given [`WidthPolicy`] strategy.
+    #[cfg(feature = "policy")]
+    pub fn with_policy(s: &'a str, policy: Option<&WidthPolicy>) -> Self {
+        Self::from_iter(s, grapheme_widths_iter_with_policy(s, policy))
+    }
+
+    fn from_iter(s: &'a str, iter: GraphemeWidths<'a>) -> Self {
+        let (lower, _) = iter.size_hint();
+        let mut bytes = Vec::with_capacity(lower + 1);
+        let mut columns = Vec::with_capacity(lower + 1);
+        let mut total = 0;
+
+        for (i, _, w, column) in iter {
+            bytes.push(i);
+            columns.push(column);
+            total = column + w;
+        }
+
+        bytes.push(s.len());
+        columns.push(total);
+
+        Self { s, bytes, columns }
+    }
+
+    /// Returns the original string.
+    pub fn as_str(&self) -> &'a str {
+        self.s
+    }
+
+    /// Returns the total display width in `O(1)`.
+    pub fn width(&self) -> usize {
+        self.columns[self.columns.len() - 1]
+    }
+
+    /// Returns the number of grapheme clusters.
+    pub fn grapheme_count(&self) -> usize {
+        self.bytes.len() - 1
+    }
+
+    /// Truncates to `max_width` without cutting graphemes.
+    ///
+    /// Same as [`truncate_by_width`](crate::truncate_by_width).
+    pub fn truncate(&self, max_width: usize) -> &'a str {
+        let k = self.columns.partition_point(|&c| c <= max_width);
+
+        &self.s[..self.bytes[k - 1]]
+    }
+
+    /// Returns the part of the string occupying display columns `[start, end)`.
+    ///
+    /// Same as [`slice_by_columns`](crate::slice_by_columns).
+    pub fn slice_by_columns(&self, start: usize, end: usize) -> ColumnSlice<'a> {
+        let mut slice = ColumnSlice {
+            text: "",
+            width: 0,
+            left_pad: 0,
+            right_pad: 0,
+        };
+
+        // First grapheme starting at or after `start`; its predecessor may be cut.
+        let first = self.columns.partition_point(|&c| c < start);
+
+        if start >= end || first == self.columns.len() {
+            return slice;
+        }
+
+        if first > 0 && self.columns[first] > start {
+            slice.left_pad = self.columns[first].min(end) - start;
+        }
+
+        // First boundary at or after `end`; the grapheme before it may be cut.
+        let mut last = self.columns.partition_point(|&c| c < end);
+
+        if last == self.columns.len() {
+            last -= 1;
+        } else if self.columns[last] > end {
+            last -= 1;
+
+            if self.columns[last] >= start {
+                slice.right_pad = end - self.columns[last];
+            }
+        }
+
+        if first < last {
+            slice.text = &self.s[self.bytes[first]..self.bytes[last]];
+            slice.width = self.columns[last] - self.columns[first];
+        }
+
+        slice
+    }
+
+    /// Returns the display column at which the grapheme containing `byte` starts.
+    ///
+    /// Same as [`column_of_byte`](crate::column_of_byte).
+    pub fn column_of_byte(&self, byte: usize) -> usize {
+        let k = self.bytes.partition_point(|&b| b <= byte);
+
+        self.columns[k - 1]
+    }
+
+    /// Returns the byte offset of the grapheme boundary at display column `col`.
+    ///
+    /// Same as [`byte_at_column`](crate::byte_at_column).
+    pub fn byte_at_column(&self, col: usize, bias: Bias) -> usize {
+        let k = self.columns.partition_point(|&c| c < col);
+
+        match self.columns.get(k) {
+            None => self.s.len(),
+            Some(&c) if c == col => self.bytes[k],
+            Some(_) => match bias {
+                Bias::Left => self.bytes[k - 1],
+                Bias::Right => self.bytes[k],
+            },
+        }
+    }
+
+    /// Returns the display columns covered by the graphemes overlapping a byte range.
+    ///
+    /// Same as [`columns_of_byte_range`](crate::columns_of_byte_range).
+    pub fn columns_of_byte_range(&self, range: Range<usize>) -> Range<usize> {
+        let start = self.column_of_byte(range.start);
+
+        if range.end <= range.start {
+            return start..start;
+        }
+
+        let k = self.bytes.partition_point(|&b| b < range.end);
+
+        start..self.columns[k.min(self.columns.len() - 1)]
+    }
+
+    /// Returns an iterator of lines wrapped at `max_width`, borrowed from the original string.
+    ///
+    /// Same as [`wrap_iter`](crate::wrap_iter).
+    pub fn wrap(&self, max_width: usize) -> impl Iterator<Item = &'a str> + '_ {
+        let last = self.bytes.len() - 1;
+        let mut i = 0;
+
+        core::iter::from_fn(move || {
+            if i >= last {
+                return None;
+            }
+
+            let limit = self.columns[i] + max_width;
+            let mut j = self.columns.partition_point(|&c| c <= limit) - 1;
+
+            // A line always takes at least one grapheme, even if it is too wide.
+            if j == i {
+                j += 1;
+            }
+
+            let line = &self.s[self.bytes[i]..self.bytes[j]];
+            i = j;
+
+            Some(line)
+        })
+    }
+}
+
+impl AsRef<str> for MeasuredStr<'_> {
+    fn as_ref(&self) -> &str {
+        self.s
+    }
+}
@@ -0,0 +1,259 @@
+//! Width-aware padding and alignment helpers.
+//!
+//! `format!("{:<10}", s)` pads by `char` count, which misaligns CJK and emoji.
+//! The helpers in this module pad by display width instead:
+//!
+//! - [`pad_left`], [`pad_right`], [`pad_center`] – pad to a minimum width with any fill
+//! - [`fit`] – truncate *and* pad to exactly the requested width
+//!
+//! The fill may be any string, including multi-column graphemes (`"・"`) and
+//! dot leaders (`"."`, `" ."`). Whole fill units are repeated as far as they
+//! fit; leftover columns that cannot hold another unit are filled with spaces
+//! next to the text, so fill units line up with the outer edge across rows.
+//!
+//! All helpers return [`Cow`], borrowing the input when no padding is needed.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::truncate::take_head;
+use crate::width::get_display_width;
+#[cfg(feature = "policy")]
+use crate::width::get_display_width_with_policy;
+
+/// Horizontal alignment of text within a fixed-width field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    /// Text starts at the left edge; padding goes on the right.
+    #[default]
+    Left,
+
+    /// Text ends at the right edge; padding goes on the left.
+    Right,
+
+    /// Text is centered; an odd leftover column goes on the right.
+    Center,
+}
+
+/// Pads a string on the left to at least `width` columns, right-aligning it.
+///
+/// # Arguments
+///
+/// * `s` - The input string to pad
+/// * `width` - Minimum display width of the result
+/// * `fill` - String repeated to fill the padding (e.g. `" "`, `"."`, `"・"`)
+///
+/// # Returns
+///
+/// The padded string, or `s` itself if it is already `width` columns or wider.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::pad_left;
+///
+/// assert_eq!(pad_left("世界", 6, " "), "  世界");
+/// assert_eq!(pad_left("42", 6, "."), "....42");
+/// ```
+pub fn pad_left<'a>(s: &'a str, width: usize, fill: &str) -> Cow<'a, str> {
+    pad_impl(s, width, fill, Align::Right, &get_display_width)
+}
+
+/// Pads a string on the right to at least `width` columns, left-aligning it.
+///
+/// See [`pad_left`] for the meaning of the arguments.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::pad_right;
+///
+/// assert_eq!(pad_right("你好", 6, " "), "你好  ");
+/// assert_eq!(pad_right("目次", 9, "・"), "目次 ・・");
+/// ```
+pub fn pad_right<'a>(s: &'a str, width: usize, fill: &str) -> Cow<'a, str> {
+    pad_impl(s, width, fill, Align::Left, &get_display_width)
+}
+
+/// Pads a string on both sides to at least `width` columns, centering it.
+///
+/// When the padding is odd, the extra column goes on the right,
+/// matching the behavior of `format!("{:^w$}")`.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::pad_center;
+///
+/// assert_eq!(pad_center("👋", 5, "-"), "-👋--");
+/// ```
+pub fn pad_center<'a>(s: &'a str, width: usize, fill: &str) -> Cow<'a, str> {
+    pad_impl(s, width, fill, Align::Center, &get_display_width)
+}
+
+/// Truncates and pads a string so that it occupies exactly `width` columns.
+///
+/// Text wider than `width` is cut at a grapheme boundary (see
+/// [`truncate_by_width`](crate::truncate_by_width)); any column left over,
+/// including one freed by a wide grapheme that did not fit, is filled with
+/// spaces according to `align`.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{fit, Align};
+///
+/// assert_eq!(fit("你好世界", 5, Align::Left), "你好 ");
+/// assert_eq!(fit("abc", 5, Align::Right), "  abc");
+/// ```
+pub fn fit(s: &str, width: usize, align: Align) -> Cow<'_, str> {
+    fit_impl(s, width, align, &get_display_width)
+}
+
+/// Same as [`pad_left`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn pad_left_with_policy<'a>(
+    s: &'a str,
+    width: usize,
+    fill: &str,
+    policy: Option<&WidthPolicy>,
+) -> Cow<'a, str> {
+    pad_impl(s, width, fill, Align::Right, &|g| {
+        get_display_width_with_policy(g, policy)
+    })
+}
+
+/// Same as [`pad_right`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn pad_right_with_policy<'a>(
+    s: &'a str,
+    width: usize,
+    fill: &str,
+    policy: Option<&WidthPolicy>,
+) -> Cow<'a, str> {
+    pad_impl(s, width, fill, Align::Left, &|g| {
+        get_display_width_with_policy(g, policy)
+    })
+}
+
+/// Same as [`pad_center`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn pad_center_with_policy<'a>(
+    s: &'a str,
+    width: usize,
+    fill: &str,
+    policy: Option<&WidthPolicy>,
+) -> Cow<'a, str> {
+    pad_impl(s, width, fill, Align::Center, &|g| {
+        get_display_width_with_policy(g, policy)
+    })
+}
+
+/// Same as [`fit`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn fit_with_policy<'a>(
+    s: &'a str,
+    width: usize,
+    align: Align,
+    policy: Option<&WidthPolicy>,
+) -> Cow<'a, str> {
+    fit_impl(s, width, align, &|g| {
+        get_display_width_with_policy(g, policy)
+    })
+}
+
+/// Shared padding implementation, parameterized over the grapheme width function.
+pub(crate) fn pad_impl<'a, F>(
+    s: &'a str,
+    width: usize,
+    fill: &str,
+    align: Align,
+    width_of: &F,
+) -> Cow<'a, str>
+where
+    F: Fn(&str) -> usize,
+{
+    let text_width: usize = s.graphemes(true).map(width_of).sum();
+
+    if text_width >= width {
+        return Cow::Borrowed(s);
+    }
+
+    let padding = width - text_width;
+    let (left, right) = sides(padding, align);
+
+    let mut out = String::with_capacity(s.len() + padding * fill.len().max(1));
+    push_fill(&mut out, left, fill, width_of, false);
+    out.push_str(s);
+    push_fill(&mut out, right, fill, width_of, true);
+
+    Cow::Owned(out)
+}
+
+/// Shared fit implementation, parameterized over the grapheme width function.
+pub(crate) fn fit_impl<'a, F>(s: &'a str, width: usize, align: Align, width_of: &F) -> Cow<'a, str>
+where
+    F: Fn(&str) -> usize,
+{
+    // Truncation only ever keeps a prefix, which is returned as is if it
+    // fills the width exactly.
+    let (head, head_width) = take_head(s, width, width_of);
+    let padding = width - head_width;
+
+    if padding == 0 {
+        return Cow::Borrowed(head);
+    }
+
+    // A wide grapheme cut at the edge leaves columns to pad as well.
+    let (left, right) = sides(padding, align);
+
+    let mut out = String::with_capacity(head.len() + padding);
+    out.extend(core::iter::repeat_n(' ', left));
+    out.push_str(head);
+    out.extend(core::iter::repeat_n(' ', right));
+
+    Cow::Owned(out)
+}
+
+/// Splits `padding` columns into the left and right padding for `align`.
+fn sides(padding: usize, align: Align) -> (usize, usize) {
+    match align {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        Align::Center => (padding / 2, padding - padding / 2),
+    }
+}
+
+/// Appends `columns` columns of fill to `out`.
+///
+/// Whole fill units are repeated; the remainder is filled with spaces on
+/// the side facing the text (`text_side_first` is `true` for right padding).
+pub(crate) fn push_fill<F>(
+    out: &mut String,
+    columns: usize,
+    fill: &str,
+    width_of: &F,
+    text_side_first: bool,
+) where
+    F: Fn(&str) -> usize,
+{
+    let fill_width: usize = fill.graphemes(true).map(width_of).sum();
+
+    let (units, spaces) = match fill_width {
+        0 => (0, columns),
+        w => (columns / w, columns % w),
+    };
+
+    if text_side_first {
+        out.extend(core::iter::repeat_n(' ', spaces));
+    }
+
+    out.extend(core::iter::repeat_n(fill, units));
+
+    if !text_side_first {
+        out.extend(core::iter::repeat_n(' ', spaces));
+    }
+}
@@ -0,0 +1,179 @@
+//! Parallel batch measurement for large corpora (CSV exports, translation dumps).
+//!
+//! - [`display_widths_par`] – width of each string in a batch
+//! - [`max_column_widths_par`] – widest cell per column of a table
+//! - [`wrap_lines_par`] – wraps a large document in chunks split at hard line breaks
+//!
+//! Work is distributed with [`rayon`]. Results are deterministic and identical
+//! to the sequential functions.
+//!
+//! > **Note:** This module is only available when the `rayon` feature is enabled.
+
+use rayon::prelude::*;
+use std::vec::Vec;
+
+#[cfg(feature = "policy")]
+use crate::grapheme::policy_ext::{display_width_with_policy, wrap_lines_with_policy};
+use crate::grapheme::{display_width, wrap_lines};
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+
+/// Minimum number of bytes wrapped sequentially by one task in [`wrap_lines_par`].
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// Returns the display width of each string, measured in parallel.
+///
+/// Same as mapping [`display_width`] over `items`.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::display_widths_par;
+///
+/// assert_eq!(display_widths_par(&["abc", "你好", "👋"]), vec![3, 4, 2]);
+/// ```
+pub fn display_widths_par<S>(items: &[S]) -> Vec<usize>
+where
+    S: AsRef<str> + Sync,
+{
+    items
+        .par_iter()
+        .map(|s| display_width(s.as_ref()))
+        .collect()
+}
+
+/// Returns the maximum display width of each column across all rows, in parallel.
+///
+/// Rows may have different lengths; the result has one entry per column of the
+/// longest row.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::max_column_widths_par;
+///
+/// let rows = [vec!["id", "名前"], vec!["1", "Alice"], vec!["22"]];
+/// assert_eq!(max_column_widths_par(&rows), vec![2, 5]);
+/// ```
+pub fn max_column_widths_par<R, S>(rows: &[R]) -> Vec<usize>
+where
+    R: AsRef<[S]> + Sync,
+    S: AsRef<str> + Sync,
+{
+    max_column_widths_impl(rows, &display_width)
+}
+
+/// Wraps each hard line of a large document at `max_width`, in parallel.
+///
+/// The document is split into chunks at `\n` only, each chunk is wrapped
+/// sequentially, and the results are concatenated in order. The output is
+/// identical to [`wrap_lines`].
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{wrap_lines, wrap_lines_par};
+///
+/// let doc = "你好世界\nHello 👋\n".repeat(10_000);
+/// assert_eq!(wrap_lines_par(&doc, 4), wrap_lines(&doc, 4));
+/// ```
+pub fn wrap_lines_par(s: &str, max_width: usize) -> Vec<&str> {
+    wrap_lines_par_impl(s, &|chunk| wrap_lines(chunk, max_width))
+}
+
+/// Same as [`display_widths_par`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn display_widths_par_with_policy<S>(items: &[S], policy: Option<&WidthPolicy>) -> Vec<usize>
+where
+    S: AsRef<str> + Sync,
+{
+    items
+        .par_iter()
+        .map(|s| display_width_with_policy(s.as_ref(), policy))
+        .collect()
+}
+
+/// Same as [`max_column_widths_par`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn max_column_widths_par_with_policy<R, S>(
+    rows: &[R],
+    policy: Option<&WidthPolicy>,
+) -> Vec<usize>
+where
+    R: AsRef<[S]> + Sync,
+    S: AsRef<str> + Sync,
+{
+    max_column_widths_impl(rows, &|s| display_width_with_policy(s, policy))
+}
+
+/// Same as [`wrap_lines_par`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn wrap_lines_par_with_policy<'a>(
+    s: &'a str,
+    max_width: usize,
+    policy: Option<&WidthPolicy>,
+) -> Vec<&'a str> {
+    wrap_lines_par_impl(s, &|chunk| wrap_lines_with_policy(chunk, max_width, policy))
+}
+
+fn max_column_widths_impl<R, S, F>(rows: &[R], width_of: &F) -> Vec<usize>
+where
+    R: AsRef<[S]> + Sync,
+    S: AsRef<str> + Sync,
+    F: Fn(&str) -> usize + Sync,
+{
+    rows.par_iter()
+        .map(|row| {
+            row.as_ref()
+                .iter()
+                .map(|cell| width_of(cell.as_ref()))
+                .collect::<Vec<_>>()
+        })
+        .reduce(Vec::new, |mut acc, row| {
+            if acc.len() < row.len() {
+                acc.resize(row.len(), 0);
+            }
+
+            for (max, w) in acc.iter_mut().zip(row) {
+                *max = (*max).max(w);
+            }
+
+            acc
+        })
+}
+
+fn wrap_lines_par_impl<'a, F>(s: &'a str, wrap: &F) -> Vec<&'a str>
+where
+    F: Fn(&'a str) -> Vec<&'a str> + Sync,
+{
+    let chunks: Vec<Vec<&str>> = hard_line_chunks(s).into_par_iter().map(wrap).collect();
+
+    chunks.concat()
+}
+
+/// Splits `s` at `\n` into chunks of at least [`CHUNK_SIZE`] bytes (except the last).
+///
+/// The separating `\n` or `\r\n` is dropped, so wrapping each chunk with
+/// [`wrap_lines`] and concatenating equals wrapping `s` as a whole.
+fn hard_line_chunks(s: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = s;
+
+    while rest.len() > CHUNK_SIZE {
+        // `\n` never occurs inside a multi-byte UTF-8 sequence.
+        let Some(i) = rest.as_bytes()[CHUNK_SIZE..]
+            .iter()
+            .position(|&b| b == b'\n')
+        else {
+            break;
+        };
+
+        let end = CHUNK_SIZE + i;
+        let chunk = &rest[..end];
+        chunks.push(chunk.strip_suffix('\r').unwrap_or(chunk));
+        rest = &rest[end + 1..];
+    }
+
+    chunks.push(rest);
+    chunks
+}
@@ -0,0 +1,171 @@
+//! Path-aware truncation for file names and directories.
+//!
+//! Generic truncation cuts wherever the width budget runs out, which for paths
+//! usually means losing the most useful part: the file name. [`truncate_path`]
+//! instead elides whole directory components first, and only cuts inside a
+//! component as a last resort.
+//!
+//! Components are separated by `/` (Unix style). Non-UTF-8 input is accepted
+//! through [`OsStr`] and converted lossily.
+
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::format;
+use std::string::String;
+use std::vec::Vec;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::truncate::{TruncateOptions, truncate_with_impl};
+use crate::width::get_display_width;
+#[cfg(feature = "policy")]
+use crate::width::get_display_width_with_policy;
+
+/// Marker used in place of elided path components and truncated names.
+const ELLIPSIS: &str = "…";
+
+/// Path separator recognized between components.
+const SEPARATOR: char = '/';
+
+/// Truncates a path to `max_width` columns, preserving the file name as long as possible.
+///
+/// The following strategies are tried in order, returning the first that fits:
+///
+/// 1. The full path, unchanged
+/// 2. The first component, an ellipsis, and as many trailing components as fit
+///    (`/home/…/プロジェクト/main.rs`)
+/// 3. An ellipsis followed by the file name (`…/main.rs`)
+/// 4. The file name truncated inside its stem, keeping the extension (`…/very_lo….rs`)
+/// 5. The file name truncated at the end
+///
+/// # Arguments
+///
+/// * `path` - Any path-like value (`&str`, `&Path`, `&OsStr`, ...), converted lossily
+/// * `max_width` - Maximum allowed display width in terminal columns
+///
+/// # Returns
+///
+/// The truncated path, borrowed when it already fits and is valid UTF-8.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::truncate_path;
+/// use std::path::Path;
+///
+/// let path = Path::new("/home/user/dev/プロジェクト/main.rs");
+/// assert_eq!(truncate_path(path, 30), "/home/…/プロジェクト/main.rs");
+/// assert_eq!(truncate_path(path, 12), "…/main.rs");
+/// ```
+pub fn truncate_path<P>(path: &P, max_width: usize) -> Cow<'_, str>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    truncate_path_impl(
+        path.as_ref().to_string_lossy(),
+        max_width,
+        &get_display_width,
+    )
+}
+
+/// Same as [`truncate_path`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn truncate_path_with_policy<'a, P>(
+    path: &'a P,
+    max_width: usize,
+    policy: Option<&WidthPolicy>,
+) -> Cow<'a, str>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    truncate_path_impl(path.as_ref().to_string_lossy(), max_width, &|g| {
+        get_display_width_with_policy(g, policy)
+    })
+}
+
+/// Shared implementation, parameterized over the grapheme width function.
+fn truncate_path_impl<'a, F>(path: Cow<'a, str>, max_width: usize, width_of: &F) -> Cow<'a, str>
+where
+    F: Fn(&str) -> usize,
+{
+    let width = |s: &str| -> usize { s.graphemes(true).map(width_of).sum() };
+
+    if width(&path) <= max_width {
+        return path;
+    }
+
+    let root = if path.starts_with(SEPARATOR) { "/" } else { "" };
+    let parts: Vec<&str> = path.split(SEPARATOR).filter(|c| !c.is_empty()).collect();
+
+    let Some((&file, dirs)) = parts.split_last() else {
+        return Cow::Owned(truncate_name(&path, max_width, width_of));
+    };
+
+    // Strategy 2: keep the first component and collapse the middle ones,
+    // re-adding trailing directories (nearest to the file) while they fit.
+    if let Some((&first, middle)) = dirs.split_first() {
+        let head = format!("{root}{first}{SEPARATOR}{ELLIPSIS}");
+        let mut tail = format!("{SEPARATOR}{file}");
+
+        if width(&head) + width(&tail) <= max_width {
+            for dir in middle.iter().rev() {
+                let candidate = format!("{SEPARATOR}{dir}{tail}");
+
+                if width(&head) + width(&candidate) > max_width {
+                    break;
+                }
+
+                tail = candidate;
+            }
+
+            return Cow::Owned(head + &tail);
+        }
+    }
+
+    // Strategy 3: elide every directory.
+    let prefix = if dirs.is_empty() && root.is_empty() {
+        String::new()
+    } else {
+        format!("{ELLIPSIS}{SEPARATOR}")
+    };
+    let prefix_width = width(&prefix);
+
+    if prefix_width + width(file) <= max_width {
+        return Cow::Owned(prefix + file);
+    }
+
+    // Strategies 4 and 5: cut inside the file name, dropping the elided
+    // prefix when it would leave no room for at least one grapheme.
+    let ellipsis_width = width(ELLIPSIS);
+
+    if !prefix.is_empty() && max_width > prefix_width + ellipsis_width {
+        return Cow::Owned(prefix + &truncate_name(file, max_width - prefix_width, width_of));
+    }
+
+    Cow::Owned(truncate_name(file, max_width, width_of))
+}
+
+/// Truncates a single component, keeping its extension when there is room for it.
+fn truncate_name<F>(name: &str, max_width: usize, width_of: &F) -> String
+where
+    F: Fn(&str) -> usize,
+{
+    let opts = TruncateOptions::end().with_ellipsis(ELLIPSIS);
+
+    // A leading dot marks a hidden file, not an extension.
+    if let Some((stem, ext)) = name.rsplit_once('.').filter(|(stem, _)| !stem.is_empty()) {
+        let ext_width: usize = ext.graphemes(true).map(width_of).sum::<usize>() + 1;
+        let ellipsis_width: usize = ELLIPSIS.graphemes(true).map(width_of).sum();
+
+        // Require at least one visible grapheme of the stem besides the ellipsis.
+        if max_width > ext_width + ellipsis_width {
+            let stem = truncate_with_impl(stem, max_width - ext_width, &opts, width_of);
+            return format!("{}.{ext}", stem.text);
+        }
+    }
+
+    truncate_with_impl(name, max_width, &opts, width_of)
+        .text
+        .into_owned()
+}
@@ -29,7 +29,7 @@
 /// (emoji, CJK, fullwidth symbols) is treated at runtime.
 ///
 /// Requires enabling the `policy` feature.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WidthPolicy {
     /// Width for emoji graphemes (e.g., 😄, 🧑‍🤝‍🧑)
     pub emoji: usize,
@@ -0,0 +1,187 @@
+//! Mapping between byte offsets and display columns.
+//!
+//! Editors and TUIs constantly convert between positions in the string buffer
+//! (byte offsets) and positions on screen (columns): placing the cursor,
+//! resolving a mouse click, or highlighting a search match.
+//!
+//! - [`column_of_byte`] – byte offset → column
+//! - [`byte_at_column`] – column → byte offset (hit-testing), with a [`Bias`]
+//! - [`columns_of_byte_range`] – byte range → column range (highlight spans)
+//!
+//! All conversions work on grapheme cluster boundaries: offsets that fall inside a
+//! cluster snap to it, and columns that fall inside a wide grapheme are resolved
+//! by the given [`Bias`].
+
+use core::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::width::get_display_width;
+#[cfg(feature = "policy")]
+use crate::width::get_display_width_with_policy;
+
+/// Which side to snap to when a column lands inside a wide grapheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Bias {
+    /// Snap to the start of the grapheme (the cell to the left).
+    #[default]
+    Left,
+
+    /// Snap to the end of the grapheme (the cell to the right).
+    Right,
+}
+
+/// Returns the display column at which the grapheme containing `byte` starts.
+///
+/// Offsets inside a multi-byte character or grapheme cluster snap to the start
+/// of that cluster. Offsets at or past the end of `s` return the total width.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::column_of_byte;
+///
+/// let s = "你a1👇"; // bytes: 你 0..3, a 3, 1 4, 👇 5..9
+/// assert_eq!(column_of_byte(s, 3), 2);
+/// assert_eq!(column_of_byte(s, 6), 4); // inside 👇
+/// assert_eq!(column_of_byte(s, 9), 6);
+/// ```
+pub fn column_of_byte(s: &str, byte: usize) -> usize {
+    column_of_byte_impl(s, byte, &get_display_width)
+}
+
+/// Returns the byte offset of the grapheme boundary at display column `col`.
+///
+/// `bias` only matters when `col` falls strictly inside a wide grapheme:
+/// [`Bias::Left`] returns the start of that grapheme, [`Bias::Right`] its end.
+/// Otherwise, the first boundary at `col` is returned. Columns at or past the
+/// total width return `s.len()`.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{byte_at_column, Bias};
+///
+/// let s = "你a1👇";
+/// assert_eq!(byte_at_column(s, 2, Bias::Left), 3);
+/// assert_eq!(byte_at_column(s, 1, Bias::Left), 0); // inside 你
+/// assert_eq!(byte_at_column(s, 1, Bias::Right), 3);
+/// ```
+pub fn byte_at_column(s: &str, col: usize, bias: Bias) -> usize {
+    byte_at_column_impl(s, col, bias, &get_display_width)
+}
+
+/// Returns the display columns covered by the graphemes overlapping a byte range.
+///
+/// The range is widened to grapheme boundaries on both sides, so the result
+/// always spans whole cells and can be used directly for highlighting.
+/// An empty byte range, including one whose start is past its end, yields an
+/// empty column range at the column of its start.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::columns_of_byte_range;
+///
+/// let s = "你a1👇";
+/// assert_eq!(columns_of_byte_range(s, 3..5), 2..4); // "a1"
+/// assert_eq!(columns_of_byte_range(s, 1..4), 0..3); // partial 你 + "a"
+/// ```
+pub fn columns_of_byte_range(s: &str, range: Range<usize>) -> Range<usize> {
+    columns_of_byte_range_impl(s, range, &get_display_width)
+}
+
+/// Same as [`column_of_byte`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn column_of_byte_with_policy(s: &str, byte: usize, policy: Option<&WidthPolicy>) -> usize {
+    column_of_byte_impl(s, byte, &|g| get_display_width_with_policy(g, policy))
+}
+
+/// Same as [`byte_at_column`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn byte_at_column_with_policy(
+    s: &str,
+    col: usize,
+    bias: Bias,
+    policy: Option<&WidthPolicy>,
+) -> usize {
+    byte_at_column_impl(s, col, bias, &|g| get_display_width_with_policy(g, policy))
+}
+
+/// Same as [`columns_of_byte_range`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn columns_of_byte_range_with_policy(
+    s: &str,
+    range: Range<usize>,
+    policy: Option<&WidthPolicy>,
+) -> Range<usize> {
+    columns_of_byte_range_impl(s, range, &|g| get_display_width_with_policy(g, policy))
+}
+
+fn column_of_byte_impl<F>(s: &str, byte: usize, width_of: &F) -> usize
+where
+    F: Fn(&str) -> usize,
+{
+    let mut column = 0;
+
+    for (i, g) in s.grapheme_indices(true) {
+        if byte < i + g.len() {
+            break;
+        }
+
+        column += width_of(g);
+    }
+
+    column
+}
+
+fn byte_at_column_impl<F>(s: &str, col: usize, bias: Bias, width_of: &F) -> usize
+where
+    F: Fn(&str) -> usize,
+{
+    let mut column = 0;
+
+    for (i, g) in s.grapheme_indices(true) {
+        if column >= col {
+            return i;
+        }
+
+        let next = column + width_of(g);
+
+        if next > col {
+            // `col` lies strictly inside this grapheme.
+            return match bias {
+                Bias::Left => i,
+                Bias::Right => i + g.len(),
+            };
+        }
+
+        column = next;
+    }
+
+    s.len()
+}
+
+fn columns_of_byte_range_impl<F>(s: &str, range: Range<usize>, width_of: &F) -> Range<usize>
+where
+    F: Fn(&str) -> usize,
+{
+    let start = column_of_byte_impl(s, range.start, width_of);
+
+    if range.end <= range.start {
+        return start..start;
+    }
+
+    let mut column = 0;
+
+    for (i, g) in s.grapheme_indices(true) {
+        if range.end <= i {
+            break;
+        }
+
+        column += width_of(g);
+    }
+
+    start..column
+}
@@ -0,0 +1,282 @@
+//! Shell prompt measurement with non-printing region markers.
+//!
+//! Line editors compute the cursor position from the prompt's visible width.
+//! Escape sequences must be enclosed in markers telling the shell they print
+//! nothing, or line editing is corrupted after the first wide character:
+//!
+//! | [`Shell`]                     | Markers                          | Literal escape |
+//! |-------------------------------|----------------------------------|----------------|
+//! | [`Bash`](Shell::Bash)         | `\[` … `\]`, `\001` … `\002`     | `\\` → `\`     |
+//! | [`Zsh`](Shell::Zsh)           | `%{` … `%}`                      | `%%` → `%`     |
+//! | [`Readline`](Shell::Readline) | `\001` … `\002`                  | –              |
+//!
+//! - [`prompt_width`] – Visible width of a prompt, skipping marked regions and raw escape codes
+//! - [`mark_prompt_escapes`] – Encloses raw escape sequences in the shell's markers
+//!
+//! Other prompt escapes (`\w`, `%~`, …) are not expanded; pass prompts after
+//! expansion, or with those escapes already replaced by their values.
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+use crate::ansi::{Segment, segments};
+#[cfg(feature = "policy")]
+use crate::grapheme::policy_ext::display_width_with_policy;
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+
+/// The marker syntax of a shell or line editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+    /// Bash `PS1`: `\[` … `\]` (and readline's `\001` … `\002`).
+    Bash,
+
+    /// Zsh `PROMPT`: `%{` … `%}`.
+    Zsh,
+
+    /// GNU readline (`rl_expand_prompt`), e.g. Python's `input()`: `\001` … `\002`.
+    Readline,
+}
+
+impl Shell {
+    /// Returns the markers that open and close a non-printing region.
+    pub fn markers(self) -> (&'static str, &'static str) {
+        match self {
+            Shell::Bash => ("\\[", "\\]"),
+            Shell::Zsh => ("%{", "%}"),
+            Shell::Readline => ("\x01", "\x02"),
+        }
+    }
+
+    /// Returns the character the shell decodes in prompts, including inside
+    /// marked regions, and which must therefore be doubled there.
+    fn quote(self) -> Option<u8> {
+        match self {
+            Shell::Bash => Some(b'\\'),
+            Shell::Zsh => Some(b'%'),
+            Shell::Readline => None,
+        }
+    }
+}
+
+/// A part of a prompt string.
+enum Piece<'a> {
+    /// Unmarked text, possibly containing raw escape sequences.
+    Text(&'a str),
+
+    /// A literal escape (`\\`, `%%`) and the character it prints.
+    Literal(&'a str, &'a str),
+
+    /// A marked non-printing region, markers included.
+    Hidden(&'a str),
+}
+
+/// Iterator splitting a prompt into [`Piece`]s.
+struct Pieces<'a> {
+    s: &'a str,
+    pos: usize,
+    shell: Shell,
+}
+
+impl<'a> Pieces<'a> {
+    /// Returns the piece starting at byte `i` if it is not plain text.
+    fn special(&self, i: usize) -> Option<Piece<'a>> {
+        let rest = &self.s[i..];
+        let hidden = |close: &str, open_len: usize| {
+            let bytes = rest.as_bytes();
+            let quote = self.shell.quote();
+            let mut j = open_len;
+
+            // The shell decodes `\\` / `%%` inside regions too, so the second
+            // character of such a pair never starts the closing marker.
+            let end = loop {
+                match (bytes.get(j), bytes.get(j + 1)) {
+                    (None, _) => break rest.len(),
+                    _ if bytes[j..].starts_with(close.as_bytes()) => break j + close.len(),
+                    (Some(&a), Some(&b)) if Some(a) == quote && a == b => j += 2,
+                    _ => j += 1,
+                }
+            };
+            Piece::Hidden(&rest[..end])
+        };
+
+        match (self.shell, rest.as_bytes()) {
+            (Shell::Bash, [b'\\', b'[', ..]) => Some(hidden("\\]", 2)),
+            (Shell::Bash, [b'\\', b'\\', ..]) => Some(Piece::Literal(&rest[..2], &rest[1..2])),
+            (Shell::Zsh, [b'%', b'{', ..]) => Some(hidden("%}", 2)),
+            (Shell::Zsh, [b'%', b'%', ..]) => Some(Piece::Literal(&rest[..2], &rest[1..2])),
+            (Shell::Bash | Shell::Readline, [0x01, ..]) => Some(hidden("\x02", 1)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Iterator for Pieces<'a> {
+    type Item = Piece<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos;
+
+        if start >= self.s.len() {
+            return None;
+        }
+
+        if let Some(piece) = self.special(start) {
+            self.pos += match piece {
+                Piece::Text(s) | Piece::Literal(s, _) | Piece::Hidden(s) => s.len(),
+            };
+            return Some(piece);
+        }
+
+        // Markers are ASCII, so they can only start at char boundaries.
+        let end = (start + 1..self.s.len())
+            .find(|&i| self.s.is_char_boundary(i) && self.special(i).is_some())
+            .unwrap_or(self.s.len());
+
+        self.pos = end;
+        Some(Piece::Text(&self.s[start..end]))
+    }
+}
+
+fn pieces(s: &str, shell: Shell) -> Pieces<'_> {
+    Pieces { s, pos: 0, shell }
+}
+
+/// Returns the visible width of a shell prompt.
+///
+/// Marked regions count as zero columns, as do raw escape sequences outside
+/// them (see [`display_width_ansi`](crate::display_width_ansi)). Literal
+/// escapes count as the character they print. As in the shell, a doubled
+/// `\\` or `%%` inside a region is a literal and cannot close it.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{Shell, prompt_width};
+///
+/// let bash = "\\[\\e[1;34m\\]~/项目\\[\\e[0m\\] 🚀 \\\\$ ";
+/// assert_eq!(prompt_width(bash, Shell::Bash), 13);
+///
+/// let zsh = "%{\x1b[32m%}100%% 完了%{\x1b[0m%} ";
+/// assert_eq!(prompt_width(zsh, Shell::Zsh), 10);
+/// ```
+pub fn prompt_width(s: &str, shell: Shell) -> usize {
+    prompt_width_impl(s, shell, &crate::display_width)
+}
+
+/// Same as [`prompt_width`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn prompt_width_with_policy(s: &str, shell: Shell, policy: Option<&WidthPolicy>) -> usize {
+    prompt_width_impl(s, shell, &|text| display_width_with_policy(text, policy))
+}
+
+fn prompt_width_impl(s: &str, shell: Shell, width_of: &dyn Fn(&str) -> usize) -> usize {
+    pieces(s, shell)
+        .map(|piece| match piece {
+            Piece::Text(text) => segments(text)
+                .map(|segment| match segment {
+                    Segment::Text(text) => width_of(text),
+                    Segment::Escape(_) => 0,
+                    Segment::Sized(sizing) => sizing.cells(width_of),
+                })
+                .sum(),
+            Piece::Literal(_, printed) => width_of(printed),
+            Piece::Hidden(_) => 0,
+        })
+        .sum()
+}
+
+/// Encloses raw escape sequences in the non-printing markers of `shell`.
+///
+/// Consecutive escape sequences share one marked region. The delimiters of a
+/// kitty text-sizing sequence are marked and its text is left visible. The
+/// shell still decodes `\\` (bash) and `%%` (zsh) inside marked regions, so
+/// backslashes and percent signs in escape sequences (e.g. the ST terminator
+/// `ESC \`, or `%20` in an OSC 8 URL) are doubled.
+///
+/// Regions that are already marked, literal escapes, and all other text are
+/// left unchanged, so the conversion is idempotent. Returns the input borrowed
+/// when there is nothing to mark.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{Shell, mark_prompt_escapes, prompt_width};
+///
+/// let raw = "\x1b[1m\x1b[34m~/日本\x1b[0m $ ";
+/// let ps1 = mark_prompt_escapes(raw, Shell::Bash);
+///
+/// assert_eq!(ps1, "\\[\x1b[1m\x1b[34m\\]~/日本\\[\x1b[0m\\] $ ");
+/// assert_eq!(prompt_width(&ps1, Shell::Bash), 9);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn mark_prompt_escapes(s: &str, shell: Shell) -> Cow<'_, str> {
+    let unmarked = pieces(s, shell).any(|piece| match piece {
+        Piece::Text(text) => segments(text).any(|seg| !matches!(seg, Segment::Text(_))),
+        _ => false,
+    });
+
+    if !unmarked {
+        return Cow::Borrowed(s);
+    }
+
+    let (open, close) = shell.markers();
+    let mut out = String::with_capacity(s.len() + 16);
+
+    for piece in pieces(s, shell) {
+        let text = match piece {
+            Piece::Text(text) => text,
+            Piece::Literal(raw, _) | Piece::Hidden(raw) => {
+                out.push_str(raw);
+                continue;
+            }
+        };
+
+        let mut marked = false;
+
+        for segment in segments(text) {
+            let (hidden, visible, rest) = match segment {
+                Segment::Text(text) => ("", text, ""),
+                Segment::Escape(escape) => (escape, "", ""),
+                Segment::Sized(sizing) => {
+                    let (prefix, suffix) = sizing.delimiters();
+                    (prefix, sizing.text, suffix)
+                }
+            };
+
+            // Only the text of a text-sizing sequence is visible.
+            for (part, is_escape) in [(hidden, true), (visible, false), (rest, true)] {
+                if part.is_empty() {
+                    continue;
+                }
+
+                if is_escape != marked {
+                    out.push_str(if is_escape { open } else { close });
+                    marked = is_escape;
+                }
+
+                match (is_escape, shell.quote()) {
+                    (true, Some(quote)) => push_doubled(&mut out, part, quote as char),
+                    _ => out.push_str(part),
+                }
+            }
+        }
+
+        if marked {
+            out.push_str(close);
+        }
+    }
+
+    Cow::Owned(out)
+}
+
+/// Appends `s` with every `quote` character doubled.
+#[cfg(feature = "alloc")]
+fn push_doubled(out: &mut String, s: &str, quote: char) {
+    for c in s.chars() {
+        if c == quote {
+            out.push(c);
+        }
+        out.push(c);
+    }
+}
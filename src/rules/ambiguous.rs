@@ -0,0 +1,23 @@
+//! Static table of East Asian Ambiguous characters (e.g., `±`, `→`, `①`, `Ω`).
+//!
+//! Characters with `East_Asian_Width=A` in UAX #11 are one column wide in most
+//! terminals but two in CJK legacy encodings and in terminals configured for
+//! them, so their width is contested. Private-use code points, whose width
+//! depends entirely on the font (e.g. Nerd Font icons), are included.
+//!
+//! The table is generated from `src/assets/east_asian_ambiguous.json`, which
+//! records the Unicode version of its source (see
+//! `scripts/gen_east_asian_ambiguous.py`). The width engine does not consult
+//! it; it only marks graphemes whose rendered width may disagree with runefix.
+
+use super::tables::in_ranges;
+
+// `AMBIGUOUS`: inclusive code point ranges with `East_Asian_Width=A`.
+include!(concat!(env!("OUT_DIR"), "/ambiguous.rs"));
+
+/// Returns `true` if the grapheme starts with an East Asian Ambiguous character.
+pub(crate) fn is_ambiguous(g: &str) -> bool {
+    g.chars()
+        .next()
+        .is_some_and(|c| in_ranges(c as u32, AMBIGUOUS))
+}
@@ -1,34 +1,20 @@
-use once_cell::sync::Lazy;
-use serde_json::Value;
-use std::collections::HashSet;
+//! Static table of fullwidth CJK Unified Ideographs.
+//!
+//! This table includes commonly used Chinese characters (Hanzi),
+//! compiled by `build.rs` from the contents of `cjk_unified.json`.
+//!
+//! Without the `cjk-ext` feature, only the URO block (U+4E00..U+9FFF) and the
+//! Compatibility Ideographs are kept from the dataset; Extension A and the
+//! Supplementary and Tertiary Ideographic Planes are matched as whole blocks.
+//!
+//! Used internally by the width engine to determine whether
+//! a grapheme should be rendered as double-width in terminal environments.
 
-/// Static set of fullwidth CJK Unified Ideographs.
-///
-/// This set includes commonly used Chinese characters (Hanzi),
-/// based on the contents of `cjk_unified.json`, embedded at compile time.
-///
-/// Used internally by the width engine to determine whether
-/// a grapheme should be rendered as double-width in terminal environments.
-static CJK_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
-    let mut set: HashSet<&'static str> = HashSet::new();
-
-    // Embed JSON data at compile time
-    let json = include_str!("../assets/cjk_unified.json");
-
-    // Parse JSON and extract keys
-    let map: Value = serde_json::from_str(json).expect("Invalid cjk_unified.json");
-
-    for (k, _) in map.as_object().unwrap() {
-        // Leak to create static lifetime for fast lookup
-        set.insert(Box::leak(k.clone().into_boxed_str()));
-    }
-
-    set
-});
+use super::tables::CJK_UNIFIED;
 
 /// Returns `true` if the given grapheme is a fullwidth CJK Unified Ideograph.
 ///
-/// This check is based on a static hash set populated from
+/// This check is a binary search over the code point ranges built from
 /// `cjk_unified.json`. The match is exact and reflects characters
 /// considered double-width in East Asian terminals.
 ///
@@ -40,5 +26,5 @@ static CJK_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
 ///
 /// `true` if the grapheme is in the CJK fullwidth set.
 pub(crate) fn is_cjk(g: &str) -> bool {
-    CJK_SET.contains(g)
+    CJK_UNIFIED.contains(g.as_bytes())
 }
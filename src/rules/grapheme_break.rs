@@ -0,0 +1,66 @@
+//! Static grapheme cluster break tables (UAX #29), for segmenting in `const fn`.
+//!
+//! The tables are generated from `src/assets/grapheme_break.json`, which is
+//! extracted from the `unicode-segmentation` crate used at runtime (see
+//! `scripts/gen_grapheme_break.py`), so both segmenters agree on the same
+//! Unicode version.
+
+use super::tables::in_ranges;
+
+/// `Grapheme_Cluster_Break` property value, plus the properties UAX #29
+/// consults alongside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GraphemeBreak {
+    Other,
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    ExtendedPictographic,
+    /// `Indic_Conjunct_Break=Consonant`.
+    InCbConsonant,
+}
+
+// `GRAPHEME_BREAK`: sorted `(start, end, class)` ranges, `Other` omitted.
+// `INCB_EXTEND`, `INCB_LINKER`: `Indic_Conjunct_Break=Extend` and `=Linker` ranges.
+include!(concat!(env!("OUT_DIR"), "/grapheme_break.rs"));
+
+/// Returns the grapheme break class of `cp`.
+pub(crate) const fn grapheme_break(cp: u32) -> GraphemeBreak {
+    let mut lo = 0;
+    let mut hi = GRAPHEME_BREAK.len();
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let (start, end, class) = GRAPHEME_BREAK[mid];
+
+        if cp < start {
+            hi = mid;
+        } else if cp > end {
+            lo = mid + 1;
+        } else {
+            return class;
+        }
+    }
+
+    GraphemeBreak::Other
+}
+
+/// Returns `true` for `Indic_Conjunct_Break=Linker` (viramas).
+pub(crate) const fn is_incb_linker(cp: u32) -> bool {
+    in_ranges(cp, INCB_LINKER)
+}
+
+/// Returns `true` for `Indic_Conjunct_Break=Extend`.
+pub(crate) const fn is_incb_extend(cp: u32) -> bool {
+    in_ranges(cp, INCB_EXTEND)
+}
@@ -1,30 +1,15 @@
-use once_cell::sync::Lazy;
-use serde_json::Value;
-use std::collections::HashSet;
+//! Static table of fullwidth Japanese kana characters (Hiragana + Katakana).
+//!
+//! This table includes both Hiragana and Katakana syllables commonly used in Japanese text,
+//! compiled by `build.rs` from `japanese_kana.json`.
+//!
+//! Without the `kana` feature (on by default), the dataset is replaced by the
+//! Hiragana, Katakana, and Kana Supplement/Extension blocks.
+//!
+//! Used internally to identify kana graphemes that should be rendered as double-width
+//! in terminal environments.
 
-/// Static set of fullwidth Japanese kana characters (Hiragana + Katakana).
-///
-/// This set includes both Hiragana and Katakana syllables commonly used in Japanese text,
-/// loaded from `japanese_kana.json` and embedded at compile time.
-///
-/// Used internally to identify kana graphemes that should be rendered as double-width
-/// in terminal environments.
-static KANA_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
-    let mut set: HashSet<&'static str> = HashSet::new();
-
-    // Embed JSON data at compile time
-    let json = include_str!("../assets/japanese_kana.json");
-
-    // Parse JSON and extract keys
-    let map: Value = serde_json::from_str(json).expect("Invalid japanese_kana.json");
-
-    for (k, _) in map.as_object().unwrap() {
-        // Leak boxed string to obtain a &'static str
-        set.insert(Box::leak(k.clone().into_boxed_str()));
-    }
-
-    set
-});
+use super::tables::JAPANESE_KANA;
 
 /// Returns `true` if the provided grapheme is a fullwidth Japanese kana character.
 ///
@@ -39,5 +24,5 @@ static KANA_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
 ///
 /// `true` if the grapheme is a fullwidth kana character.
 pub(crate) fn is_kana(g: &str) -> bool {
-    KANA_SET.contains(g)
+    JAPANESE_KANA.contains(g.as_bytes())
 }
@@ -1,6 +1,10 @@
+#[cfg(feature = "alloc")]
+pub mod ambiguous;
 pub mod cjk;
 pub mod emoji;
+pub(crate) mod grapheme_break;
 pub mod hangul;
 pub mod kana;
 pub mod punct;
+pub(crate) mod tables;
 pub mod variants;
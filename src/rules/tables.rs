@@ -0,0 +1,140 @@
+//! Static lookup tables generated by `build.rs` from `src/assets/*.json`.
+//!
+//! Each [`Table`] stores single characters as sorted code point ranges and
+//! multi-codepoint sequences as one dense byte blob. Lookups are binary
+//! searches usable in `const fn`. No parsing or allocation happens at runtime.
+//!
+//! `WIDE_CHARS` lists the single-character entries of all tables as sorted,
+//! inclusive code point ranges, for lookups in `const fn`.
+
+use core::cmp::Ordering;
+
+/// A width dataset in compact form.
+pub(crate) struct Table {
+    /// Single characters, as sorted inclusive code point ranges.
+    pub(crate) ranges: &'static [(u32, u32)],
+
+    /// Multi-codepoint sequences, concatenated in `str` order.
+    pub(crate) sequences: &'static [u8],
+
+    /// End offset of each sequence in [`sequences`](Self::sequences).
+    pub(crate) ends: &'static [u32],
+}
+
+impl Table {
+    /// Returns `true` if the UTF-8 encoded grapheme `g` is in the table.
+    pub(crate) const fn contains(&self, g: &[u8]) -> bool {
+        if g.is_empty() {
+            return false;
+        }
+
+        let (c, len) = decode(g, 0);
+
+        if len == g.len() {
+            in_ranges(c as u32, self.ranges)
+        } else {
+            self.contains_sequence(g)
+        }
+    }
+
+    const fn contains_sequence(&self, g: &[u8]) -> bool {
+        let mut lo = 0;
+        let mut hi = self.ends.len();
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let start = if mid == 0 {
+                0
+            } else {
+                self.ends[mid - 1] as usize
+            };
+            let (_, rest) = self.sequences.split_at(start);
+            let (entry, _) = rest.split_at(self.ends[mid] as usize - start);
+
+            match compare(entry, g) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return true,
+            }
+        }
+
+        false
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/tables.rs"));
+
+/// Returns `true` if `cp` lies in one of the sorted, inclusive `ranges`.
+pub(crate) const fn in_ranges(cp: u32, ranges: &[(u32, u32)]) -> bool {
+    let mut lo = 0;
+    let mut hi = ranges.len();
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let (start, end) = ranges[mid];
+
+        if cp < start {
+            hi = mid;
+        } else if cp > end {
+            lo = mid + 1;
+        } else {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Decodes the `char` starting at byte `i` of valid UTF-8, with its length.
+pub(crate) const fn decode(bytes: &[u8], i: usize) -> (char, usize) {
+    let b0 = bytes[i] as u32;
+
+    let (cp, len) = if b0 < 0x80 {
+        (b0, 1)
+    } else if b0 < 0xE0 {
+        ((b0 & 0x1F) << 6 | (bytes[i + 1] as u32 & 0x3F), 2)
+    } else if b0 < 0xF0 {
+        (
+            (b0 & 0x0F) << 12 | (bytes[i + 1] as u32 & 0x3F) << 6 | (bytes[i + 2] as u32 & 0x3F),
+            3,
+        )
+    } else {
+        (
+            (b0 & 0x07) << 18
+                | (bytes[i + 1] as u32 & 0x3F) << 12
+                | (bytes[i + 2] as u32 & 0x3F) << 6
+                | (bytes[i + 3] as u32 & 0x3F),
+            4,
+        )
+    };
+
+    match char::from_u32(cp) {
+        Some(c) => (c, len),
+        None => ('\u{FFFD}', len),
+    }
+}
+
+/// Lexicographic byte comparison, which equals `str` ordering for UTF-8.
+const fn compare(a: &[u8], b: &[u8]) -> Ordering {
+    let mut i = 0;
+
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return if a[i] < b[i] {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+        }
+
+        i += 1;
+    }
+
+    if a.len() < b.len() {
+        Ordering::Less
+    } else if a.len() > b.len() {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
@@ -0,0 +1,192 @@
+//! Column-range slicing for horizontal scrolling.
+//!
+//! [`slice_by_columns`] extracts the part of a string that occupies display
+//! columns `[start, end)`, as seen by a viewport scrolled horizontally.
+//!
+//! Graphemes are never split. When a wide grapheme straddles either edge of
+//! the range, it is left out and the columns it would have covered inside the
+//! range are reported as padding, so the caller can draw a filler (a space,
+//! `…`, `>`) and keep the viewport exactly `end - start` columns wide.
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "alloc")]
+use crate::pad::push_fill;
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::width::get_display_width;
+#[cfg(feature = "policy")]
+use crate::width::get_display_width_with_policy;
+
+/// The part of a string visible within a column range.
+///
+/// Returned by [`slice_by_columns`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnSlice<'a> {
+    /// Graphemes lying entirely inside the range, borrowed from the input.
+    pub text: &'a str,
+
+    /// Display width of [`text`](Self::text).
+    pub width: usize,
+
+    /// Columns at the start of the range covered by a grapheme cut at the left edge.
+    pub left_pad: usize,
+
+    /// Columns at the end of the range covered by a grapheme cut at the right edge.
+    pub right_pad: usize,
+}
+
+impl<'a> ColumnSlice<'a> {
+    /// Renders the slice with its padding columns filled with `filler`.
+    ///
+    /// The filler is measured and repeated as many whole times as fit; any
+    /// remaining columns are filled with spaces, so the result is always
+    /// `left_pad + width + right_pad` columns wide. Returns the borrowed text
+    /// when there is no padding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use runefix_core::slice_by_columns;
+    ///
+    /// let slice = slice_by_columns("你好世界", 1, 7);
+    /// assert_eq!(slice.fill_with("."), ".好世.");
+    /// assert_eq!(slice.fill_with("…"), " 好世 "); // `…` is 2 columns wide
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn fill_with(&self, filler: &str) -> Cow<'a, str> {
+        self.fill_with_impl(filler, &get_display_width)
+    }
+
+    /// Same as [`fill_with`](Self::fill_with), but measures `filler` with the given [`WidthPolicy`] strategy.
+    #[cfg(all(feature = "alloc", feature = "policy"))]
+    pub fn fill_with_policy(&self, filler: &str, policy: Option<&WidthPolicy>) -> Cow<'a, str> {
+        self.fill_with_impl(filler, &|g| get_display_width_with_policy(g, policy))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn fill_with_impl<F>(&self, filler: &str, width_of: &F) -> Cow<'a, str>
+    where
+        F: Fn(&str) -> usize,
+    {
+        if self.left_pad == 0 && self.right_pad == 0 {
+            return Cow::Borrowed(self.text);
+        }
+
+        let mut out = String::with_capacity(
+            self.text.len() + (self.left_pad + self.right_pad) * filler.len(),
+        );
+        push_fill(&mut out, self.left_pad, filler, width_of, false);
+        out.push_str(self.text);
+        push_fill(&mut out, self.right_pad, filler, width_of, true);
+
+        Cow::Owned(out)
+    }
+}
+
+/// Returns the substring occupying display columns `[start, end)`.
+///
+/// Columns are counted from `0` at the beginning of `s`. Wide graphemes that
+/// straddle `start` or `end` are excluded, and the columns they cover inside
+/// the range are reported in [`ColumnSlice::left_pad`] and
+/// [`ColumnSlice::right_pad`]. If the text ends before `end`, the slice is
+/// simply narrower; no padding is reported for the missing columns.
+///
+/// # Arguments
+///
+/// * `s` - The input string (typically one line)
+/// * `start` - First visible column (inclusive)
+/// * `end` - Column just past the last visible one (exclusive)
+///
+/// # Returns
+///
+/// A [`ColumnSlice`] borrowing the visible graphemes from `s`.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::slice_by_columns;
+///
+/// // "你" covers columns 0–1, "a" 2, "1" 3, "👇" 4–5
+/// let slice = slice_by_columns("你a1👇", 1, 5);
+/// assert_eq!(slice.text, "a1");
+/// assert_eq!((slice.left_pad, slice.right_pad), (1, 1));
+/// assert_eq!(slice.fill_with(" "), " a1 ");
+/// ```
+pub fn slice_by_columns(s: &str, start: usize, end: usize) -> ColumnSlice<'_> {
+    slice_by_columns_impl(s, start, end, &get_display_width)
+}
+
+/// Same as [`slice_by_columns`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn slice_by_columns_with_policy<'a>(
+    s: &'a str,
+    start: usize,
+    end: usize,
+    policy: Option<&WidthPolicy>,
+) -> ColumnSlice<'a> {
+    slice_by_columns_impl(s, start, end, &|g| get_display_width_with_policy(g, policy))
+}
+
+/// Shared implementation, parameterized over the grapheme width function.
+fn slice_by_columns_impl<'a, F>(
+    s: &'a str,
+    start: usize,
+    end: usize,
+    width_of: &F,
+) -> ColumnSlice<'a>
+where
+    F: Fn(&str) -> usize,
+{
+    let mut slice = ColumnSlice {
+        text: "",
+        width: 0,
+        left_pad: 0,
+        right_pad: 0,
+    };
+
+    if start >= end {
+        return slice;
+    }
+
+    let mut column = 0;
+    let mut byte = 0;
+    let mut start_byte = None;
+    let mut end_byte = s.len();
+
+    for g in s.graphemes(true) {
+        let w = width_of(g);
+        let next = column + w;
+
+        if column >= end {
+            end_byte = byte;
+            break;
+        }
+
+        if column < start {
+            // Cut at the left edge: count the columns it covers inside the range.
+            if next > start {
+                slice.left_pad = next.min(end) - start;
+            }
+        } else if next > end {
+            // Cut at the right edge.
+            slice.right_pad = end - column;
+            end_byte = byte;
+            break;
+        } else {
+            start_byte.get_or_insert(byte);
+            slice.width += w;
+        }
+
+        column = next;
+        byte += g.len();
+    }
+
+    if let Some(start_byte) = start_byte {
+        slice.text = &s[start_byte..end_byte];
+    }
+
+    slice
+}
@@ -0,0 +1,199 @@
+//! Incremental measurement of byte streams.
+//!
+//! Logs being tailed and PTY output arrive in arbitrary byte chunks, which may
+//! split a UTF-8 sequence or a grapheme cluster (`e` + U+0301, a ZWJ emoji
+//! sequence) in two. [`StreamMeasurer`] buffers both kinds of incomplete input
+//! and only emits a grapheme once the next one has started, so the emitted
+//! widths always sum to [`display_width`](crate::display_width) of the
+//! concatenated (lossily decoded) input.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+#[cfg(not(feature = "policy"))]
+use crate::width::get_display_width;
+
+/// A stateful measurer fed with arbitrary `&[u8]` chunks.
+///
+/// Invalid UTF-8 is replaced with U+FFFD, as in [`String::from_utf8_lossy`].
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::StreamMeasurer;
+///
+/// let mut m = StreamMeasurer::new();
+/// let bytes = "a👩\u{200d}💻".as_bytes();
+///
+/// // The chunk ends inside the 4-byte 👩, so `a` may still be extended.
+/// assert_eq!(m.feed(&bytes[..3]), vec![]);
+/// assert_eq!(m.feed(&bytes[3..]), vec![("a".to_string(), 1)]);
+/// assert_eq!(m.flush(), vec![("👩\u{200d}💻".to_string(), 2)]);
+/// assert_eq!(m.column(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamMeasurer {
+    /// Trailing bytes of an incomplete UTF-8 sequence.
+    bytes: Vec<u8>,
+
+    /// Decoded text whose last grapheme may still be extended.
+    text: String,
+
+    /// Column on the current line.
+    column: usize,
+
+    /// Total width emitted so far.
+    width: usize,
+
+    #[cfg(feature = "policy")]
+    policy: WidthPolicy,
+}
+
+impl Default for StreamMeasurer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamMeasurer {
+    /// Creates a measurer using the default terminal strategy.
+    pub fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            text: String::new(),
+            column: 0,
+            width: 0,
+            #[cfg(feature = "policy")]
+            policy: WidthPolicy::terminal(),
+        }
+    }
+
+    /// Creates a measurer using the given [`WidthPolicy`] strategy.
+    #[cfg(feature = "policy")]
+    pub fn with_policy(policy: Option<&WidthPolicy>) -> Self {
+        Self {
+            policy: policy.cloned().unwrap_or_else(WidthPolicy::terminal),
+            ..Self::new()
+        }
+    }
+
+    /// Feeds a chunk of bytes and returns the graphemes it completed, with their widths.
+    ///
+    /// The last grapheme seen is held back until more input (or [`flush`](Self::flush))
+    /// shows that it cannot be extended.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<(String, usize)> {
+        self.bytes.extend_from_slice(chunk);
+        self.decode();
+        self.drain(false)
+    }
+
+    /// Treats the input seen so far as complete and returns all buffered graphemes.
+    ///
+    /// An incomplete trailing UTF-8 sequence is emitted as U+FFFD. Feeding more
+    /// input afterwards starts a new grapheme.
+    pub fn flush(&mut self) -> Vec<(String, usize)> {
+        if !self.bytes.is_empty() {
+            self.bytes.clear();
+            self.text.push(char::REPLACEMENT_CHARACTER);
+        }
+
+        self.drain(true)
+    }
+
+    /// Returns the display column after the last emitted grapheme.
+    ///
+    /// The column resets to `0` after a grapheme containing `\n`.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the total width of all emitted graphemes.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns `true` if no input is buffered.
+    pub fn is_idle(&self) -> bool {
+        self.bytes.is_empty() && self.text.is_empty()
+    }
+
+    /// Returns the buffered grapheme that has not been emitted yet, with its width.
+    #[cfg(feature = "std")]
+    pub(crate) fn pending(&self) -> (&str, usize) {
+        (&self.text, self.width_of(&self.text))
+    }
+
+    /// Moves all complete UTF-8 from `bytes` into `text`.
+    fn decode(&mut self) {
+        let mut pos = 0;
+
+        while pos < self.bytes.len() {
+            match str::from_utf8(&self.bytes[pos..]) {
+                Ok(s) => {
+                    self.text.push_str(s);
+                    pos = self.bytes.len();
+                }
+                Err(e) => {
+                    let valid = &self.bytes[pos..pos + e.valid_up_to()];
+                    self.text
+                        .push_str(str::from_utf8(valid).unwrap_or_default());
+                    pos += e.valid_up_to();
+
+                    match e.error_len() {
+                        Some(len) => {
+                            self.text.push(char::REPLACEMENT_CHARACTER);
+                            pos += len;
+                        }
+                        // Incomplete sequence at the end: wait for more bytes.
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        self.bytes.drain(..pos);
+    }
+
+    /// Emits buffered graphemes, keeping the last one unless `all` is set.
+    fn drain(&mut self, all: bool) -> Vec<(String, usize)> {
+        let mut events = Vec::new();
+        let mut graphemes = self.text.grapheme_indices(true).peekable();
+        let mut end = 0;
+
+        while let Some((i, g)) = graphemes.next() {
+            if !all && graphemes.peek().is_none() {
+                break;
+            }
+
+            events.push((g.to_string(), self.width_of(g)));
+            end = i + g.len();
+        }
+
+        self.text.drain(..end);
+
+        for (g, w) in &events {
+            self.width += w;
+            self.column = match g.contains('\n') {
+                true => 0,
+                false => self.column + w,
+            };
+        }
+
+        events
+    }
+
+    #[cfg(not(feature = "policy"))]
+    fn width_of(&self, g: &str) -> usize {
+        get_display_width(g)
+    }
+
+    #[cfg(feature = "policy")]
+    fn width_of(&self, g: &str) -> usize {
+        self.policy.resolve_width(g)
+    }
+}
@@ -0,0 +1,437 @@
+//! Style-preserving truncation and wrapping of ANSI-colored text.
+//!
+//! - [`truncate_ansi`] – Truncates by width, then closes any open style and hyperlink
+//! - [`wrap_ansi`] – Wraps by width; every line is self-contained
+//!
+//! Escape sequences are parsed as in [`display_width_ansi`](crate::display_width_ansi)
+//! and never cut. While scanning, the active SGR state (attributes, colors)
+//! and OSC 8 hyperlink are tracked, so that each output line can be closed with
+//! `ESC [0m` / `ESC ]8;;ESC \` and the next one reopened with the same style.
+//! The reopened SGR sequence is rebuilt from the state, not replayed, so it
+//! stays short however many sequences preceded it.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::mem;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ansi::{Segment, segments};
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::width::get_display_width;
+#[cfg(feature = "policy")]
+use crate::width::get_display_width_with_policy;
+
+const SGR_RESET: &str = "\x1b[0m";
+const OSC8_CLOSE: &str = "\x1b]8;;\x1b\\";
+
+/// On/off SGR attributes: set code and the codes that clear it.
+const ATTRIBUTES: &[(u16, &[u16])] = &[
+    (1, &[22]),  // bold
+    (2, &[22]),  // dim
+    (3, &[23]),  // italic
+    (5, &[25]),  // slow blink
+    (6, &[25]),  // rapid blink
+    (7, &[27]),  // inverse
+    (8, &[28]),  // hidden
+    (9, &[29]),  // strikethrough
+    (21, &[24]), // double underline
+    (53, &[55]), // overline
+];
+
+/// A color set by SGR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    /// A 16-color code (`30..=37`, `90..=97`, or their background equivalents).
+    Code(u16),
+
+    /// A 256-color palette index (`38;5;n`).
+    Indexed(u8),
+
+    /// A direct color (`38;2;r;g;b`).
+    Rgb(u8, u8, u8),
+}
+
+/// Active SGR state and hyperlink, rebuilt as escape sequences are scanned.
+#[derive(Debug, Clone, Default)]
+struct Style<'a> {
+    /// Bit `i` is set if `ATTRIBUTES[i]` is on.
+    attributes: u16,
+
+    /// Underline style: `1` for `4`, `n` for `4:n`.
+    underline: Option<u16>,
+
+    foreground: Option<Color>,
+    background: Option<Color>,
+    underline_color: Option<Color>,
+
+    /// The OSC 8 sequence that opened the current hyperlink.
+    link: Option<&'a str>,
+}
+
+impl<'a> Style<'a> {
+    /// Updates the state with an escape sequence; other sequences are ignored.
+    fn apply(&mut self, escape: &'a str) {
+        if let Some(params) = sgr_params(escape) {
+            self.apply_sgr(params);
+        } else if let Some(uri) = osc8_uri(escape) {
+            self.link = (!uri.is_empty()).then_some(escape);
+        }
+    }
+
+    fn apply_sgr(&mut self, params: &str) {
+        let mut params = params.split(';');
+
+        while let Some(param) = params.next() {
+            let mut sub = param.split(':');
+            let code = number(sub.next().unwrap_or_default());
+
+            match code {
+                0 => {
+                    *self = Style {
+                        link: self.link,
+                        ..Style::default()
+                    }
+                }
+                4 => {
+                    self.underline = match sub.next().map(number) {
+                        None => Some(1),
+                        Some(0) => None,
+                        Some(style) => Some(style),
+                    }
+                }
+                24 => {
+                    self.underline = None;
+                    self.set_attribute(21, false);
+                }
+                30..=37 | 90..=97 => self.foreground = Some(Color::Code(code)),
+                40..=47 | 100..=107 => self.background = Some(Color::Code(code)),
+                38 | 48 | 58 => {
+                    let color = if param.contains(':') {
+                        extended_color(&mut sub, true)
+                    } else {
+                        extended_color(&mut params, false)
+                    };
+
+                    match code {
+                        38 => self.foreground = color,
+                        48 => self.background = color,
+                        _ => self.underline_color = color,
+                    }
+                }
+                39 => self.foreground = None,
+                49 => self.background = None,
+                59 => self.underline_color = None,
+                _ => {
+                    for &(set, clear) in ATTRIBUTES {
+                        if code == set {
+                            self.set_attribute(set, true);
+                        } else if clear.contains(&code) {
+                            self.set_attribute(set, false);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn set_attribute(&mut self, code: u16, on: bool) {
+        if let Some(i) = ATTRIBUTES.iter().position(|&(set, _)| set == code) {
+            if on {
+                self.attributes |= 1 << i;
+            } else {
+                self.attributes &= !(1 << i);
+            }
+        }
+    }
+
+    fn has_sgr(&self) -> bool {
+        self.attributes != 0
+            || self.underline.is_some()
+            || self.foreground.is_some()
+            || self.background.is_some()
+            || self.underline_color.is_some()
+    }
+
+    /// Appends the sequences that reestablish this style.
+    fn open(&self, out: &mut String) {
+        if let Some(link) = self.link {
+            out.push_str(link);
+        }
+
+        if !self.has_sgr() {
+            return;
+        }
+
+        let mut params = String::new();
+
+        for (i, &(code, _)) in ATTRIBUTES.iter().enumerate() {
+            if self.attributes & (1 << i) != 0 {
+                let _ = write!(params, "{code};");
+            }
+        }
+
+        match self.underline {
+            Some(1) => params.push_str("4;"),
+            Some(style) => {
+                let _ = write!(params, "4:{style};");
+            }
+            None => {}
+        }
+
+        for (base, color) in [
+            (38, self.foreground),
+            (48, self.background),
+            (58, self.underline_color),
+        ] {
+            let _ = match color {
+                Some(Color::Code(code)) => write!(params, "{code};"),
+                Some(Color::Indexed(n)) => write!(params, "{base};5;{n};"),
+                Some(Color::Rgb(r, g, b)) => write!(params, "{base};2;{r};{g};{b};"),
+                None => Ok(()),
+            };
+        }
+
+        params.pop();
+        let _ = write!(out, "\x1b[{params}m");
+    }
+
+    /// Appends the sequences that end this style.
+    fn close(&self, out: &mut String) {
+        if self.has_sgr() {
+            out.push_str(SGR_RESET);
+        }
+
+        if self.link.is_some() {
+            out.push_str(OSC8_CLOSE);
+        }
+    }
+}
+
+/// Returns the parameters of an SGR sequence (`CSI … m`), if `escape` is one.
+fn sgr_params(escape: &str) -> Option<&str> {
+    let body = escape
+        .strip_prefix("\x1b[")
+        .or_else(|| escape.strip_prefix('\u{9b}'))?
+        .strip_suffix('m')?;
+
+    body.bytes()
+        .all(|b| b.is_ascii_digit() || b == b';' || b == b':')
+        .then_some(body)
+}
+
+/// Returns the URI of an OSC 8 hyperlink sequence, if `escape` is one.
+///
+/// An empty URI closes the current hyperlink.
+fn osc8_uri(escape: &str) -> Option<&str> {
+    let body = escape
+        .strip_prefix("\x1b]")
+        .or_else(|| escape.strip_prefix('\u{9d}'))?;
+    let body = body
+        .strip_suffix('\x07')
+        .or_else(|| body.strip_suffix("\x1b\\"))
+        .or_else(|| body.strip_suffix('\u{9c}'))
+        .unwrap_or(body);
+
+    let (_, uri) = body.strip_prefix("8;")?.split_once(';')?;
+    Some(uri)
+}
+
+/// Parses the color following `38`, `48`, or `58`.
+///
+/// In the colon form (`38:2::r:g:b`), the direct color may carry a color space ID.
+fn extended_color<'s>(params: &mut impl Iterator<Item = &'s str>, colon: bool) -> Option<Color> {
+    let channel = |s: Option<&str>| number(s.unwrap_or_default()).min(255) as u8;
+
+    match number(params.next()?) {
+        5 => Some(Color::Indexed(channel(params.next()))),
+        2 => {
+            let mut values = [None; 4];
+            let count = if colon { 4 } else { 3 };
+
+            for value in values.iter_mut().take(count) {
+                *value = params.next();
+            }
+
+            let rgb = match values {
+                [_, Some(r), Some(g), Some(b)] if colon => [r, g, b],
+                [Some(r), Some(g), Some(b), _] => [r, g, b],
+                _ => return None,
+            };
+
+            Some(Color::Rgb(
+                channel(Some(rgb[0])),
+                channel(Some(rgb[1])),
+                channel(Some(rgb[2])),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn number(s: &str) -> u16 {
+    s.parse().unwrap_or(0)
+}
+
+/// Truncates ANSI-colored text to `max_width` visible columns.
+///
+/// Escape sequences are never cut and do not count toward the width. If text
+/// is removed, any SGR style or OSC 8 hyperlink open at the cut point is
+/// closed. Returns the input borrowed when it already fits.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::truncate_ansi;
+///
+/// assert_eq!(
+///     truncate_ansi("\x1b[1;31m你好世界\x1b[0m", 5),
+///     "\x1b[1;31m你好\x1b[0m"
+/// );
+/// assert_eq!(truncate_ansi("\x1b[32mok\x1b[0m", 5), "\x1b[32mok\x1b[0m");
+/// ```
+pub fn truncate_ansi(s: &str, max_width: usize) -> Cow<'_, str> {
+    truncate_ansi_impl(s, max_width, &get_display_width)
+}
+
+/// Same as [`truncate_ansi`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn truncate_ansi_with_policy<'a>(
+    s: &'a str,
+    max_width: usize,
+    policy: Option<&WidthPolicy>,
+) -> Cow<'a, str> {
+    truncate_ansi_impl(s, max_width, &|g| get_display_width_with_policy(g, policy))
+}
+
+/// Wraps ANSI-colored text into lines of at most `max_width` visible columns.
+///
+/// Each hard line (separated by `\n`) is wrapped independently, as with
+/// [`wrap_lines`](crate::wrap_lines). Every output line is self-contained:
+/// the style and hyperlink active at its start are reopened, and those still
+/// active at its end are closed, so lines can be printed, scrolled, or
+/// reordered without colors bleeding.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::wrap_ansi;
+///
+/// let lines = wrap_ansi("\x1b[31mred 红色\x1b[0m", 4);
+/// assert_eq!(lines, vec!["\x1b[31mred \x1b[0m", "\x1b[31m红色\x1b[0m"]);
+/// ```
+pub fn wrap_ansi(s: &str, max_width: usize) -> Vec<String> {
+    wrap_ansi_impl(s, max_width, &get_display_width)
+}
+
+/// Same as [`wrap_ansi`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn wrap_ansi_with_policy(
+    s: &str,
+    max_width: usize,
+    policy: Option<&WidthPolicy>,
+) -> Vec<String> {
+    wrap_ansi_impl(s, max_width, &|g| get_display_width_with_policy(g, policy))
+}
+
+fn truncate_ansi_impl<'a>(
+    s: &'a str,
+    max_width: usize,
+    width_of: &dyn Fn(&str) -> usize,
+) -> Cow<'a, str> {
+    let mut style = Style::default();
+    let mut column = 0;
+    let mut offset = 0;
+
+    for segment in segments(s) {
+        match segment {
+            Segment::Escape(escape) => style.apply(escape),
+            Segment::Sized(sizing) => {
+                let w = sizing.cells(width_of);
+
+                if column + w > max_width {
+                    let mut out = String::from(&s[..offset]);
+                    style.close(&mut out);
+                    return Cow::Owned(out);
+                }
+
+                column += w;
+            }
+            Segment::Text(text) => {
+                for (i, g) in text.grapheme_indices(true) {
+                    let w = width_of(g);
+
+                    if column + w > max_width {
+                        let mut out = String::from(&s[..offset + i]);
+                        style.close(&mut out);
+                        return Cow::Owned(out);
+                    }
+
+                    column += w;
+                }
+            }
+        }
+
+        offset += segment.as_str().len();
+    }
+
+    Cow::Borrowed(s)
+}
+
+fn wrap_ansi_impl(s: &str, max_width: usize, width_of: &dyn Fn(&str) -> usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut style = Style::default();
+    let mut line = String::new();
+    let mut column = 0;
+
+    for segment in segments(s) {
+        let text = match segment {
+            Segment::Escape(escape) => {
+                line.push_str(escape);
+                style.apply(escape);
+                continue;
+            }
+            Segment::Sized(sizing) => {
+                let w = sizing.cells(width_of);
+
+                if column > 0 && column + w > max_width {
+                    style.close(&mut line);
+                    lines.push(mem::take(&mut line));
+                    style.open(&mut line);
+                    column = 0;
+                }
+
+                line.push_str(sizing.raw);
+                column += w;
+                continue;
+            }
+            Segment::Text(text) => text,
+        };
+
+        for g in text.graphemes(true) {
+            let newline = g == "\n" || g == "\r\n";
+            let w = if newline { 0 } else { width_of(g) };
+
+            // A line always takes at least one grapheme, even if it is too wide.
+            if newline || (column > 0 && column + w > max_width) {
+                style.close(&mut line);
+                lines.push(mem::take(&mut line));
+                style.open(&mut line);
+                column = 0;
+
+                if newline {
+                    continue;
+                }
+            }
+
+            line.push_str(g);
+            column += w;
+        }
+    }
+
+    style.close(&mut line);
+    lines.push(line);
+    lines
+}
@@ -0,0 +1,285 @@
+//! Rope-friendly width summaries for incremental measurement.
+//!
+//! Editors store buffers as ropes or chunk lists and cannot afford to call
+//! [`display_width`](crate::display_width) over the whole document after every
+//! keystroke. [`WidthSummary`] is a monoid: it is computed per chunk and
+//! combined associatively, so a rope can cache one summary per node and update
+//! only the path to an edited leaf.
+//!
+//! ## Clusters straddling chunk boundaries
+//!
+//! A grapheme cluster may span two chunks — a ZWJ emoji sequence, a combining
+//! mark, or a pair of regional indicators split across leaves. Each summary
+//! therefore keeps the text before its first and after its last
+//! *context-free* boundary unmeasured; when two summaries are combined, that
+//! pending text is joined and segmented again.
+//!
+//! A boundary is context-free when no text to its left can remove it. Per
+//! UAX #29, only three rules look further left than the adjacent pair:
+//!
+//! - GB11 (emoji ZWJ sequences) — boundary preceded by U+200D ZWJ
+//! - GB12/13 (regional indicator pairs) — boundary preceded by a regional indicator
+//! - GB9c (Indic conjuncts) — boundary followed by an Indic consonant
+//!
+//! Every other boundary inside a chunk is final, and the clusters between two
+//! such boundaries are measured once.
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+#[cfg(not(feature = "policy"))]
+use crate::width::get_display_width;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Line statistics of a fully segmented span of text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct LineStats {
+    /// Total display width, including any width assigned to line breaks.
+    width: usize,
+
+    /// Number of `\n` characters.
+    newlines: usize,
+
+    /// Width after the last `\n` (or of the whole span if there is none).
+    last_line_width: usize,
+}
+
+impl LineStats {
+    fn then(self, next: Self) -> Self {
+        Self {
+            width: self.width + next.width,
+            newlines: self.newlines + next.newlines,
+            last_line_width: match next.newlines {
+                0 => self.last_line_width + next.width,
+                _ => next.last_line_width,
+            },
+        }
+    }
+}
+
+/// A measured span enclosed by context-free boundaries, followed by pending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Body {
+    stats: LineStats,
+    tail: String,
+}
+
+/// An associative summary of display width and line structure for a chunk of text.
+///
+/// Summaries of adjacent chunks combine into the summary of their concatenation,
+/// regardless of grouping, and the identity is the summary of `""`
+/// ([`WidthSummary::default()`]):
+///
+/// ```rust
+/// use runefix_core::{WidthSummary, display_width};
+///
+/// // A ZWJ sequence (👩‍💻) split across two chunks
+/// let chunks = ["Hi 👩\u{200d}", "💻\n世界"];
+/// let total = chunks
+///     .iter()
+///     .map(|c| WidthSummary::new(c))
+///     .fold(WidthSummary::default(), |acc, s| acc.combine(&s));
+///
+/// assert_eq!(total.width(), display_width("Hi 👩\u{200d}💻\n世界"));
+/// assert_eq!(total.line_count(), 2);
+/// assert_eq!(total.last_line_width(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WidthSummary {
+    /// Text before the first context-free boundary (all text if there is none).
+    head: String,
+
+    /// Measured middle span and trailing pending text, if a boundary was found.
+    body: Option<Body>,
+
+    #[cfg(feature = "policy")]
+    policy: WidthPolicy,
+}
+
+impl Default for WidthSummary {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl WidthSummary {
+    /// Summarizes a chunk using the default terminal strategy.
+    pub fn new(chunk: &str) -> Self {
+        Self::from_chunk(
+            chunk,
+            #[cfg(feature = "policy")]
+            WidthPolicy::terminal(),
+        )
+    }
+
+    /// Summarizes a chunk using the given [`WidthPolicy`] strategy.
+    ///
+    /// Only summaries built with the same policy should be combined.
+    #[cfg(feature = "policy")]
+    pub fn with_policy(chunk: &str, policy: Option<&WidthPolicy>) -> Self {
+        Self::from_chunk(chunk, policy.cloned().unwrap_or_else(WidthPolicy::terminal))
+    }
+
+    fn from_chunk(chunk: &str, #[cfg(feature = "policy")] policy: WidthPolicy) -> Self {
+        let mut summary = Self {
+            head: String::new(),
+            body: None,
+            #[cfg(feature = "policy")]
+            policy,
+        };
+        summary.set_text(chunk);
+        summary
+    }
+
+    /// Splits `text` at its first and last context-free boundaries.
+    fn set_text(&mut self, text: &str) {
+        let mut boundaries = text
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .filter(|&i| i > 0 && is_context_free(text, i));
+
+        let Some(first) = boundaries.next() else {
+            self.head = text.to_owned();
+            self.body = None;
+            return;
+        };
+        let last = boundaries.next_back().unwrap_or(first);
+
+        self.head = text[..first].to_owned();
+        self.body = Some(Body {
+            stats: self.stats(&text[first..last]),
+            tail: text[last..].to_owned(),
+        });
+    }
+
+    /// Combines this summary with the summary of the chunk that immediately follows it.
+    ///
+    /// This operation is associative, with [`WidthSummary::default()`] as identity.
+    pub fn combine(&self, next: &Self) -> Self {
+        if self.is_empty() {
+            return next.clone();
+        }
+
+        if next.is_empty() {
+            return self.clone();
+        }
+
+        // Pending text on both sides of the seam is joined and re-segmented.
+        let seam = [self.pending(), next.head.as_str()].concat();
+        let mut out = self.clone();
+        out.set_text(&seam);
+
+        // The seam starts at a context-free boundary of `self`, so its head is final.
+        if let Some(left) = &self.body {
+            let (stats, tail) = match out.body.take() {
+                Some(body) => (
+                    left.stats.then(self.stats(&out.head)).then(body.stats),
+                    body.tail,
+                ),
+                None => (left.stats, core::mem::take(&mut out.head)),
+            };
+
+            out.head = self.head.clone();
+            out.body = Some(Body { stats, tail });
+        }
+
+        // The seam ends at a context-free boundary of `next`, if it has one.
+        if let Some(right) = &next.body {
+            let stats = match out.body.take() {
+                Some(body) => body.stats.then(self.stats(&body.tail)).then(right.stats),
+                None => right.stats,
+            };
+
+            out.body = Some(Body {
+                stats,
+                tail: right.tail.clone(),
+            });
+        }
+
+        out
+    }
+
+    /// Returns `true` if this is the summary of an empty chunk.
+    pub fn is_empty(&self) -> bool {
+        self.head.is_empty() && self.body.is_none()
+    }
+
+    /// Returns the display width of the summarized text.
+    pub fn width(&self) -> usize {
+        self.line_stats().width
+    }
+
+    /// Returns the number of lines (one more than the number of `\n` characters).
+    pub fn line_count(&self) -> usize {
+        self.line_stats().newlines + 1
+    }
+
+    /// Returns the display width of the text after the last `\n`.
+    pub fn last_line_width(&self) -> usize {
+        self.line_stats().last_line_width
+    }
+
+    /// Returns the trailing text whose segmentation may still change when more text follows.
+    pub fn pending(&self) -> &str {
+        match &self.body {
+            Some(body) => &body.tail,
+            None => &self.head,
+        }
+    }
+
+    fn line_stats(&self) -> LineStats {
+        match &self.body {
+            Some(body) => self
+                .stats(&self.head)
+                .then(body.stats)
+                .then(self.stats(&body.tail)),
+            None => self.stats(&self.head),
+        }
+    }
+
+    /// Segments and measures `text` on its own.
+    fn stats(&self, text: &str) -> LineStats {
+        let mut stats = LineStats::default();
+
+        for g in text.graphemes(true) {
+            let w = self.width_of(g);
+            let newlines = g.matches('\n').count();
+
+            stats.width += w;
+            stats.newlines += newlines;
+            stats.last_line_width = match newlines {
+                0 => stats.last_line_width + w,
+                _ => 0,
+            };
+        }
+
+        stats
+    }
+
+    #[cfg(not(feature = "policy"))]
+    fn width_of(&self, g: &str) -> usize {
+        get_display_width(g)
+    }
+
+    #[cfg(feature = "policy")]
+    fn width_of(&self, g: &str) -> usize {
+        self.policy.resolve_width(g)
+    }
+}
+
+/// Returns `true` if the grapheme boundary at byte `i` cannot be removed by preceding text.
+fn is_context_free(text: &str, i: usize) -> bool {
+    let prev = text[..i].chars().next_back();
+    let next = text[i..].chars().next();
+
+    match (prev, next) {
+        (Some(prev), Some(next)) => {
+            prev != '\u{200D}'
+                && !('\u{1F1E6}'..='\u{1F1FF}').contains(&prev)
+                && !('\u{0900}'..='\u{0DFF}').contains(&next)
+        }
+        _ => false,
+    }
+}
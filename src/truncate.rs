@@ -0,0 +1,261 @@
+//! Ellipsis-aware truncation with configurable placement.
+//!
+//! This module extends [`truncate_by_width`](crate::truncate_by_width) with:
+//!
+//! - Ellipsis insertion at the **end**, **start**, or **middle** of the text
+//! - A configurable ellipsis string, measured with the active width strategy
+//! - A [`Truncated`] report telling whether anything was cut and how many
+//!   columns of padding are needed to exactly fill `max_width`
+//!
+//! The padding report matters when a wide grapheme (CJK, emoji) does not fit
+//! into the remaining budget: the output is then one column narrower than
+//! requested, and callers aligning columns must fill the gap themselves.
+
+use alloc::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::width::get_display_width;
+#[cfg(feature = "policy")]
+use crate::width::get_display_width_with_policy;
+
+/// Where the ellipsis is placed when a string is truncated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncatePosition {
+    /// Keeps the head of the string: `"Hello, wor…"`.
+    #[default]
+    End,
+
+    /// Keeps the tail of the string: `"…llo, world"`.
+    Start,
+
+    /// Keeps both ends of the string: `"Hello…world"`.
+    Middle,
+}
+
+/// Options controlling [`truncate_with`].
+///
+/// The default places a `…` (U+2026) at the end of the string.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{TruncateOptions, TruncatePosition};
+///
+/// let opts = TruncateOptions::middle().with_ellipsis("...");
+/// assert_eq!(opts.position, TruncatePosition::Middle);
+/// assert_eq!(opts.ellipsis, "...");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TruncateOptions<'e> {
+    /// Where the ellipsis is inserted.
+    pub position: TruncatePosition,
+
+    /// The string inserted in place of the removed text (may be empty).
+    pub ellipsis: &'e str,
+}
+
+impl Default for TruncateOptions<'_> {
+    fn default() -> Self {
+        Self::end()
+    }
+}
+
+impl<'e> TruncateOptions<'e> {
+    /// Truncates at the end, keeping the head of the string.
+    pub fn end() -> Self {
+        Self {
+            position: TruncatePosition::End,
+            ellipsis: "…",
+        }
+    }
+
+    /// Truncates at the start, keeping the tail of the string.
+    pub fn start() -> Self {
+        Self {
+            position: TruncatePosition::Start,
+            ..Self::end()
+        }
+    }
+
+    /// Truncates in the middle, keeping both ends of the string.
+    pub fn middle() -> Self {
+        Self {
+            position: TruncatePosition::Middle,
+            ..Self::end()
+        }
+    }
+
+    /// Replaces the ellipsis string (e.g. `"..."` or `""`).
+    pub fn with_ellipsis(self, ellipsis: &'e str) -> Self {
+        Self { ellipsis, ..self }
+    }
+}
+
+/// The outcome of [`truncate_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Truncated<'a> {
+    /// The resulting text, borrowed when no truncation was necessary.
+    pub text: Cow<'a, str>,
+
+    /// Display width of [`text`](Self::text), ellipsis included.
+    pub width: usize,
+
+    /// Whether any part of the input was removed.
+    pub truncated: bool,
+
+    /// Columns left unused between `width` and the requested `max_width`.
+    pub padding: usize,
+}
+
+impl AsRef<str> for Truncated<'_> {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Truncates a string to `max_width` columns, inserting an ellipsis where text is removed.
+///
+/// Grapheme clusters are never split. The ellipsis width is measured with the same
+/// width strategy as the text, so `"…"` (2 columns in terminals) and `"..."`
+/// (3 columns) consume different budgets. If the ellipsis alone is wider than
+/// `max_width`, it is itself cut down to fit.
+///
+/// # Arguments
+///
+/// * `s` - The input string to truncate
+/// * `max_width` - Maximum allowed display width in terminal columns
+/// * `opts` - Ellipsis string and placement
+///
+/// # Returns
+///
+/// A [`Truncated`] describing the output text, its width, whether truncation
+/// happened, and how much padding is needed to reach `max_width`.
+///
+/// # Example
+///
+/// ```rust
+/// use runefix_core::{truncate_with, TruncateOptions};
+///
+/// let opts = TruncateOptions::end().with_ellipsis("...");
+/// let t = truncate_with("Hello, 世界", 8, &opts);
+/// assert_eq!(t.text, "Hello...");
+/// assert!(t.truncated);
+///
+/// let t = truncate_with("你好世界", 6, &TruncateOptions::middle().with_ellipsis("."));
+/// assert_eq!(t.text, "你.界");
+/// assert_eq!((t.width, t.padding), (5, 1));
+/// ```
+pub fn truncate_with<'a>(s: &'a str, max_width: usize, opts: &TruncateOptions) -> Truncated<'a> {
+    truncate_with_impl(s, max_width, opts, get_display_width)
+}
+
+/// Same as [`truncate_with`], but applies the given [`WidthPolicy`] strategy.
+#[cfg(feature = "policy")]
+pub fn truncate_with_policy<'a>(
+    s: &'a str,
+    max_width: usize,
+    opts: &TruncateOptions,
+    policy: Option<&WidthPolicy>,
+) -> Truncated<'a> {
+    truncate_with_impl(s, max_width, opts, |g| {
+        get_display_width_with_policy(g, policy)
+    })
+}
+
+/// Shared implementation, parameterized over the grapheme width function.
+pub(crate) fn truncate_with_impl<'a, F>(
+    s: &'a str,
+    max_width: usize,
+    opts: &TruncateOptions,
+    width_of: F,
+) -> Truncated<'a>
+where
+    F: Fn(&str) -> usize,
+{
+    let total: usize = s.graphemes(true).map(&width_of).sum();
+
+    if total <= max_width {
+        return Truncated {
+            text: Cow::Borrowed(s),
+            width: total,
+            truncated: false,
+            padding: max_width - total,
+        };
+    }
+
+    let (ellipsis, ellipsis_width) = take_head(opts.ellipsis, max_width, &width_of);
+    let budget = max_width - ellipsis_width;
+
+    let (text, width) = match opts.position {
+        TruncatePosition::End => {
+            let (head, w) = take_head(s, budget, &width_of);
+            ([head, ellipsis].concat(), w)
+        }
+        TruncatePosition::Start => {
+            let (tail, w) = take_tail(s, budget, &width_of);
+            ([ellipsis, tail].concat(), w)
+        }
+        TruncatePosition::Middle => {
+            // Favor the head on odd budgets; hand any slack left by a wide
+            // grapheme that did not fit over to the tail.
+            let (head, head_w) = take_head(s, budget.div_ceil(2), &width_of);
+            let (tail, tail_w) = take_tail(s, budget - head_w, &width_of);
+            ([head, ellipsis, tail].concat(), head_w + tail_w)
+        }
+    };
+
+    let width = width + ellipsis_width;
+
+    Truncated {
+        text: Cow::Owned(text),
+        width,
+        truncated: true,
+        padding: max_width - width,
+    }
+}
+
+/// Returns the longest grapheme-aligned prefix of `s` within `max_width`, with its width.
+pub(crate) fn take_head<'a, F>(s: &'a str, max_width: usize, width_of: &F) -> (&'a str, usize)
+where
+    F: Fn(&str) -> usize,
+{
+    let mut total_width = 0;
+    let mut end_byte = 0;
+
+    for g in s.graphemes(true) {
+        let w = width_of(g);
+
+        if total_width + w > max_width {
+            break;
+        }
+
+        total_width += w;
+        end_byte += g.len();
+    }
+
+    (&s[..end_byte], total_width)
+}
+
+/// Returns the longest grapheme-aligned suffix of `s` within `max_width`, with its width.
+fn take_tail<'a, F>(s: &'a str, max_width: usize, width_of: &F) -> (&'a str, usize)
+where
+    F: Fn(&str) -> usize,
+{
+    let mut total_width = 0;
+    let mut start_byte = s.len();
+
+    for g in s.graphemes(true).rev() {
+        let w = width_of(g);
+
+        if total_width + w > max_width {
+            break;
+        }
+
+        total_width += w;
+        start_byte -= g.len();
+    }
+
+    (&s[start_byte..], total_width)
+}
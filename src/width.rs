@@ -16,6 +16,8 @@
 
 #[cfg(feature = "policy")]
 use crate::policy::WidthPolicy;
+#[cfg(feature = "alloc")]
+use crate::rules::ambiguous::is_ambiguous;
 use crate::rules::cjk::is_cjk;
 use crate::rules::emoji::is_emoji;
 use crate::rules::hangul::is_hangul;
@@ -46,7 +48,7 @@ use crate::rules::variants::is_fullwidth_variant;
 ///     - Hangul syllables
 ///     - Fullwidth symbol variants (e.g. `Ａ`, `！`)
 ///     - Fullwidth punctuations (e.g. `。`, `、`)
-///     → width = `2`
+///       → width = `2`
 /// 4. **Emoji** (including multi-codepoint ZWJ sequences) → width = `2`
 ///
 /// Characters not matching any of the above are treated as width `1`.
@@ -87,6 +89,38 @@ pub(crate) fn get_display_width(s: &str) -> usize {
     WidthPolicy::terminal().resolve_width(s)
 }
 
+/// Returns `true` if terminals are known to disagree on the width of a grapheme.
+///
+/// This covers emoji (including flags, keycaps, and ZWJ or variation selector
+/// sequences not in the emoji table) and East Asian Ambiguous characters.
+#[cfg(feature = "alloc")]
+pub(crate) fn is_contested(g: &str) -> bool {
+    let mut chars = g.chars();
+
+    match (chars.next(), chars.next()) {
+        (None, _) => false,
+        (Some(ch), None) if ch.is_ascii() => false,
+        (Some(ch), _) => {
+            is_emoji(g)
+                || is_ambiguous(g)
+                || ('\u{1F1E6}'..='\u{1F1FF}').contains(&ch)
+                || g.contains(['\u{200D}', '\u{FE0F}'])
+        }
+    }
+}
+
+/// Returns the width [`get_display_width()`] assigns to uncategorized graphemes.
+#[cfg(not(feature = "policy"))]
+pub(crate) fn get_fallback_width() -> usize {
+    1
+}
+
+/// Returns the width [`get_display_width()`] assigns to uncategorized graphemes.
+#[cfg(feature = "policy")]
+pub(crate) fn get_fallback_width() -> usize {
+    WidthPolicy::terminal().fallback
+}
+
 /// Grapheme width lookup using a custom width policy.
 ///
 /// Requires enabling `--features policy`.
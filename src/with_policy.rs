@@ -18,16 +18,41 @@
 //! Requires the `policy` feature.
 
 // std
-use std::fmt::{self, Debug, Display, Formatter};
-use std::ops::Deref;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::{Deref, Range};
 
 // crate
+use crate::aligned::render_aligned_with_policy;
+use crate::ansi::display_width_ansi_with_policy;
+use crate::columns::{Columns, columns_with_policy};
+use crate::export::{render_html_with_policy, render_svg_with_policy};
 use crate::grapheme::graphemes;
+use crate::grapheme::iter::{
+    GraphemeWidths, WrapIter, grapheme_widths_iter_with_policy, wrap_iter_with_policy,
+};
 use crate::grapheme::policy_ext::{
     display_width_with_policy, display_widths_with_policy, grapheme_widths_with_policy,
     split_by_width_with_policy, truncate_by_width_with_policy,
 };
+use crate::kitty::{TextSizingMode, encode_text_sizing_with_policy};
+use crate::measured::MeasuredStr;
+use crate::pad::{
+    Align, fit_with_policy, pad_center_with_policy, pad_left_with_policy, pad_right_with_policy,
+};
+#[cfg(feature = "std")]
+use crate::path::truncate_path_with_policy;
 use crate::policy::WidthPolicy;
+use crate::position::{
+    Bias, byte_at_column_with_policy, column_of_byte_with_policy, columns_of_byte_range_with_policy,
+};
+use crate::prompt::{Shell, prompt_width_with_policy};
+use crate::slice::{ColumnSlice, slice_by_columns_with_policy};
+use crate::styled::{truncate_ansi_with_policy, wrap_ansi_with_policy};
+use crate::summary::WidthSummary;
+use crate::truncate::{TruncateOptions, Truncated, truncate_with_policy};
 
 /// Wraps a [`WidthPolicy`] to enable fluent reuse across multiple strings.
 /// Avoids needing to repeatedly pass the policy to each function.
@@ -69,6 +94,46 @@ impl AppliedPolicy<'_, '_> {
         display_width_with_policy(self.s, Some(self.policy))
     }
 
+    /// Returns the display width under the current policy, ignoring ANSI escape sequences.
+    pub fn display_width_ansi(&self) -> usize {
+        display_width_ansi_with_policy(self.s, Some(self.policy))
+    }
+
+    /// Returns the visible width of the string as a prompt of the given shell.
+    pub fn prompt_width(&self, shell: Shell) -> usize {
+        prompt_width_with_policy(self.s, shell, Some(self.policy))
+    }
+
+    /// Truncates ANSI-colored text by width, closing any open style and hyperlink.
+    pub fn truncate_ansi(&self, max_width: usize) -> Cow<'_, str> {
+        truncate_ansi_with_policy(self.s, max_width, Some(self.policy))
+    }
+
+    /// Wraps ANSI-colored text into self-contained lines of at most `max_width` columns.
+    pub fn wrap_ansi(&self, max_width: usize) -> Vec<String> {
+        wrap_ansi_with_policy(self.s, max_width, Some(self.policy))
+    }
+
+    /// Wraps graphemes in kitty text-sizing sequences declaring their width under the current policy.
+    pub fn encode_text_sizing(&self, mode: TextSizingMode) -> Cow<'_, str> {
+        encode_text_sizing_with_policy(self.s, mode, Some(self.policy))
+    }
+
+    /// Follows each contested grapheme with a cursor move to the column the current policy expects.
+    pub fn render_aligned(&self, start_col: usize) -> Cow<'_, str> {
+        render_aligned_with_policy(self.s, start_col, Some(self.policy))
+    }
+
+    /// Renders the string as an HTML snapshot with one fixed-width box per cell.
+    pub fn render_html(&self) -> String {
+        render_html_with_policy(self.s, Some(self.policy))
+    }
+
+    /// Renders the string as an SVG snapshot with one fixed-width box per cell.
+    pub fn render_svg(&self) -> String {
+        render_svg_with_policy(self.s, Some(self.policy))
+    }
+
     /// Returns the display width of each grapheme cluster.
     pub fn display_widths(&self) -> Vec<usize> {
         display_widths_with_policy(self.s, Some(self.policy))
@@ -79,11 +144,89 @@ impl AppliedPolicy<'_, '_> {
         grapheme_widths_with_policy(self.s, Some(self.policy))
     }
 
+    /// Returns a lazy iterator of `(byte_offset, grapheme, width, column)` tuples.
+    pub fn grapheme_widths_iter(&self) -> GraphemeWidths<'_> {
+        grapheme_widths_iter_with_policy(self.s, Some(self.policy))
+    }
+
+    /// Returns a lazy iterator of lines wrapped at `max_width`.
+    pub fn wrap_iter(&self, max_width: usize) -> WrapIter<'_> {
+        wrap_iter_with_policy(self.s, max_width, Some(self.policy))
+    }
+
+    /// Segments and measures the string once, for repeated layout queries.
+    pub fn measure(&self) -> MeasuredStr<'_> {
+        MeasuredStr::with_policy(self.s, Some(self.policy))
+    }
+
+    /// Summarizes the string as one chunk of a larger document.
+    pub fn summarize(&self) -> WidthSummary {
+        WidthSummary::with_policy(self.s, Some(self.policy))
+    }
+
+    /// Returns the display column at which the grapheme containing `byte` starts.
+    pub fn column_of_byte(&self, byte: usize) -> usize {
+        column_of_byte_with_policy(self.s, byte, Some(self.policy))
+    }
+
+    /// Returns the byte offset of the grapheme boundary at display column `col`.
+    pub fn byte_at_column(&self, col: usize, bias: Bias) -> usize {
+        byte_at_column_with_policy(self.s, col, bias, Some(self.policy))
+    }
+
+    /// Returns the display columns covered by the graphemes overlapping a byte range.
+    pub fn columns_of_byte_range(&self, range: Range<usize>) -> Range<usize> {
+        columns_of_byte_range_with_policy(self.s, range, Some(self.policy))
+    }
+
     /// Truncates the string by width without cutting grapheme boundaries.
     pub fn truncate_by_width(&self, max_width: usize) -> &str {
         truncate_by_width_with_policy(self.s, max_width, Some(self.policy))
     }
 
+    /// Truncates the string with an ellipsis, reporting width and leftover padding.
+    pub fn truncate_with(&self, max_width: usize, opts: &TruncateOptions) -> Truncated<'_> {
+        truncate_with_policy(self.s, max_width, opts, Some(self.policy))
+    }
+
+    /// Truncates the string as a `/`-separated path, eliding directories first.
+    #[cfg(feature = "std")]
+    pub fn truncate_path(&self, max_width: usize) -> Cow<'_, str> {
+        truncate_path_with_policy(self.s, max_width, Some(self.policy))
+    }
+
+    /// Pads the string on the left to at least `width` columns.
+    pub fn pad_left(&self, width: usize, fill: &str) -> Cow<'_, str> {
+        pad_left_with_policy(self.s, width, fill, Some(self.policy))
+    }
+
+    /// Pads the string on the right to at least `width` columns.
+    pub fn pad_right(&self, width: usize, fill: &str) -> Cow<'_, str> {
+        pad_right_with_policy(self.s, width, fill, Some(self.policy))
+    }
+
+    /// Pads the string on both sides to at least `width` columns.
+    pub fn pad_center(&self, width: usize, fill: &str) -> Cow<'_, str> {
+        pad_center_with_policy(self.s, width, fill, Some(self.policy))
+    }
+
+    /// Truncates and pads the string to exactly `width` columns.
+    pub fn fit(&self, width: usize, align: Align) -> Cow<'_, str> {
+        fit_with_policy(self.s, width, align, Some(self.policy))
+    }
+
+    /// Returns a [`Display`] adapter whose width and precision count columns.
+    ///
+    /// Example: `format!("{:>12}|", view.columns())`.
+    pub fn columns(&self) -> Columns<'_> {
+        columns_with_policy(self.s, Some(self.policy))
+    }
+
+    /// Returns the part of the string occupying display columns `[start, end)`.
+    pub fn slice_by_columns(&self, start: usize, end: usize) -> ColumnSlice<'_> {
+        slice_by_columns_with_policy(self.s, start, end, Some(self.policy))
+    }
+
     /// Wraps the string into lines by display width.
     pub fn split_by_width(&self, max_width: usize) -> Vec<String> {
         split_by_width_with_policy(self.s, max_width, Some(self.policy))
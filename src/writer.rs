@@ -0,0 +1,419 @@
+//! `std::io::Write` adapters that enforce a maximum display width.
+//!
+//! - [`WrapWriter`] – hard-wraps each line at `max_width` columns
+//! - [`TruncateWriter`] – clips each line at `max_width` columns
+//!
+//! Both are built on [`StreamMeasurer`], so multi-byte characters and grapheme
+//! clusters split across `write` calls are laid out as a whole. Output is
+//! written through as soon as a grapheme is known to be complete.
+//!
+//! [`flush`](Write::flush) also writes the last, possibly incomplete grapheme.
+//! If a later write extends it (e.g. with a combining mark), only the new bytes
+//! are written and the column is corrected, so the byte stream is never altered.
+//! A grapheme that grows past the end of the line this way cannot be moved,
+//! and the column stops at `max_width`.
+//!
+//! An incomplete UTF-8 sequence at the end of the input is kept across
+//! `flush`, since a later write may complete it. `into_inner` and drop treat
+//! the input as finished and write it as U+FFFD, as in
+//! [`String::from_utf8_lossy`].
+//!
+//! Once `write` has accepted bytes, they are never lost: if the underlying
+//! writer fails, the graphemes not yet written stay queued and `write` still
+//! returns `Ok`. The next `write`, `flush`, or `into_inner` writes them
+//! first and returns the error if it persists.
+//!
+//! Like [`BufWriter`](std::io::BufWriter), the writers also write the last
+//! grapheme when dropped, ignoring errors. Call `flush` or `into_inner` to
+//! handle them.
+
+use std::borrow::ToOwned;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::string::String;
+
+#[cfg(feature = "policy")]
+use crate::policy::WidthPolicy;
+use crate::stream::StreamMeasurer;
+
+/// How a grapheme that does not fit on the current line is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Overflow {
+    Wrap,
+    Clip,
+}
+
+/// Shared line layout state for both writers.
+#[derive(Debug)]
+struct Layout<W: Write> {
+    /// The underlying writer, taken only by `into_inner`.
+    inner: Option<W>,
+    measurer: StreamMeasurer,
+
+    /// Graphemes accepted from the measurer but not written yet, after an error.
+    queued: VecDeque<(String, usize)>,
+
+    overflow: Overflow,
+    max_width: usize,
+
+    /// Column on the current output line.
+    column: usize,
+
+    /// Whether the rest of the current line is being clipped.
+    clipped: bool,
+
+    /// Bytes and width of the pending grapheme already written by `flush`,
+    /// and whether it was visible.
+    provisional: Option<(usize, usize, bool)>,
+}
+
+impl<W: Write> Layout<W> {
+    fn new(inner: W, max_width: usize, overflow: Overflow, measurer: StreamMeasurer) -> Self {
+        Self {
+            inner: Some(inner),
+            measurer,
+            queued: VecDeque::new(),
+            overflow,
+            max_width,
+            column: 0,
+            clipped: false,
+            provisional: None,
+        }
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // `buf` is only accepted once earlier graphemes are out.
+        self.write_queued()?;
+        self.queued.extend(self.measurer.feed(buf));
+
+        // The measurer has consumed `buf`, so report it as written; whatever
+        // could not be placed is retried, and its error returned, next time.
+        let _ = self.write_queued();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_queued()?;
+        self.write_pending()?;
+        self.inner().flush()
+    }
+
+    /// Writes all buffered input, with an incomplete UTF-8 sequence as U+FFFD.
+    fn finish(&mut self) -> io::Result<()> {
+        self.write_queued()?;
+        self.queued.extend(self.measurer.flush());
+        self.write_queued()
+    }
+
+    /// Writes queued graphemes, keeping the rest queued on error.
+    fn write_queued(&mut self) -> io::Result<()> {
+        while let Some((g, w)) = self.queued.pop_front() {
+            if let Err(err) = self.place(&g, w) {
+                self.queued.push_front((g, w));
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the pending grapheme, which a later write may still extend.
+    fn write_pending(&mut self) -> io::Result<()> {
+        let (g, w) = self.measurer.pending();
+
+        if !g.is_empty() {
+            let g = g.to_owned();
+            let visible = self.place(&g, w)?;
+            self.provisional = Some((g.len(), w, visible));
+        }
+
+        Ok(())
+    }
+
+    fn inner(&mut self) -> &mut W {
+        self.inner.as_mut().expect("writer taken by into_inner")
+    }
+
+    fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("writer taken by into_inner")
+    }
+
+    fn take_inner(&mut self) -> W {
+        self.inner.take().expect("writer taken by into_inner")
+    }
+
+    /// Lays out a grapheme and returns whether it was written.
+    fn place(&mut self, g: &str, w: usize) -> io::Result<bool> {
+        let newline = g.contains('\n');
+
+        // Continue a grapheme partially written by an earlier flush.
+        if let Some((written, assumed, visible)) = self.provisional {
+            if visible || newline {
+                self.inner().write_all(&g.as_bytes()[written..])?;
+            }
+
+            self.provisional = None;
+
+            // The grapheme cannot move to the next line once written, so a
+            // wider result only fills the rest of this one.
+            if visible {
+                let column = (self.column + w).saturating_sub(assumed);
+                self.column = column.min(self.max_width.max(w));
+            }
+
+            self.end_line_if(newline);
+            return Ok(visible);
+        }
+
+        let fits = self.column + w <= self.max_width;
+        let visible = match self.overflow {
+            _ if newline => true,
+            Overflow::Wrap if !fits && self.column > 0 => {
+                self.inner().write_all(b"\n")?;
+                self.column = 0;
+                true
+            }
+            Overflow::Wrap => true,
+            Overflow::Clip => {
+                self.clipped |= !fits;
+                !self.clipped
+            }
+        };
+
+        if visible {
+            self.inner().write_all(g.as_bytes())?;
+            self.column += w;
+        }
+
+        self.end_line_if(newline);
+        Ok(visible)
+    }
+
+    fn end_line_if(&mut self, newline: bool) {
+        if newline {
+            self.column = 0;
+            self.clipped = false;
+        }
+    }
+}
+
+impl<W: Write> Drop for Layout<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            // Errors cannot be reported here; `flush` or `into_inner` returns them.
+            let _ = self.finish();
+        }
+    }
+}
+
+/// The error returned by `into_inner` of [`WrapWriter`] and [`TruncateWriter`]
+/// when the last grapheme cannot be written.
+///
+/// Like [`std::io::IntoInnerError`], it holds the adapter, so no input is lost.
+#[derive(Debug)]
+pub struct IntoInnerError<W>(W, io::Error);
+
+impl<W> IntoInnerError<W> {
+    /// Returns the error that caused `into_inner` to fail.
+    pub fn error(&self) -> &io::Error {
+        &self.1
+    }
+
+    /// Returns the adapter, to retry or recover the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+
+    /// Returns the error, dropping the adapter.
+    pub fn into_error(self) -> io::Error {
+        self.1
+    }
+
+    /// Returns both the error and the adapter.
+    pub fn into_parts(self) -> (io::Error, W) {
+        (self.1, self.0)
+    }
+}
+
+impl<W> From<IntoInnerError<W>> for io::Error {
+    fn from(err: IntoInnerError<W>) -> io::Error {
+        err.1
+    }
+}
+
+impl<W> Display for IntoInnerError<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.1.fmt(f)
+    }
+}
+
+impl<W: fmt::Debug> Error for IntoInnerError<W> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.1)
+    }
+}
+
+/// A [`Write`] adapter that hard-wraps each line at `max_width` display columns.
+///
+/// A `\n` is inserted before any grapheme that would overflow the current line.
+/// A grapheme wider than `max_width` is placed alone on its own line.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Write;
+/// use runefix_core::WrapWriter;
+///
+/// let mut w = WrapWriter::new(Vec::new(), 4);
+/// w.write_all("ab你".as_bytes()).unwrap();
+/// w.write_all("好\nxyz".as_bytes()).unwrap();
+/// w.flush().unwrap();
+///
+/// assert_eq!(String::from_utf8(w.into_inner().unwrap()).unwrap(), "ab你\n好\nxyz");
+/// ```
+#[derive(Debug)]
+pub struct WrapWriter<W: Write> {
+    layout: Layout<W>,
+}
+
+/// A [`Write`] adapter that clips each line at `max_width` display columns.
+///
+/// Graphemes that would overflow the current line are dropped until the next
+/// `\n`, matching [`truncate_by_width`](crate::truncate_by_width) per line.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Write;
+/// use runefix_core::TruncateWriter;
+///
+/// let mut w = TruncateWriter::new(Vec::new(), 3);
+/// w.write_all("ab你好\nxyzw".as_bytes()).unwrap();
+/// w.flush().unwrap();
+///
+/// assert_eq!(String::from_utf8(w.into_inner().unwrap()).unwrap(), "ab\nxyz");
+/// ```
+#[derive(Debug)]
+pub struct TruncateWriter<W: Write> {
+    layout: Layout<W>,
+}
+
+impl<W: Write> WrapWriter<W> {
+    /// Wraps `inner`, using the default terminal strategy.
+    pub fn new(inner: W, max_width: usize) -> Self {
+        Self {
+            layout: Layout::new(inner, max_width, Overflow::Wrap, StreamMeasurer::new()),
+        }
+    }
+
+    /// Wraps `inner`, using the given [`WidthPolicy`] strategy.
+    #[cfg(feature = "policy")]
+    pub fn with_policy(inner: W, max_width: usize, policy: Option<&WidthPolicy>) -> Self {
+        let measurer = StreamMeasurer::with_policy(policy);
+
+        Self {
+            layout: Layout::new(inner, max_width, Overflow::Wrap, measurer),
+        }
+    }
+
+    /// Returns the display column on the current output line.
+    pub fn column(&self) -> usize {
+        self.layout.column
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.layout.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly bypasses width tracking.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.layout.inner()
+    }
+
+    /// Writes all buffered input and unwraps the underlying writer.
+    ///
+    /// An incomplete trailing UTF-8 sequence is written as U+FFFD. As with [`BufWriter::into_inner`](std::io::BufWriter::into_inner), the
+    /// underlying writer is not flushed, and on error the adapter is returned
+    /// in the [`IntoInnerError`].
+    #[allow(clippy::result_large_err)] // Mirrors `BufWriter::into_inner`.
+    pub fn into_inner(mut self) -> Result<W, IntoInnerError<Self>> {
+        match self.layout.finish() {
+            Ok(()) => Ok(self.layout.take_inner()),
+            Err(err) => Err(IntoInnerError(self, err)),
+        }
+    }
+}
+
+impl<W: Write> Write for WrapWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.layout.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.layout.flush()
+    }
+}
+
+impl<W: Write> TruncateWriter<W> {
+    /// Wraps `inner`, using the default terminal strategy.
+    pub fn new(inner: W, max_width: usize) -> Self {
+        Self {
+            layout: Layout::new(inner, max_width, Overflow::Clip, StreamMeasurer::new()),
+        }
+    }
+
+    /// Wraps `inner`, using the given [`WidthPolicy`] strategy.
+    #[cfg(feature = "policy")]
+    pub fn with_policy(inner: W, max_width: usize, policy: Option<&WidthPolicy>) -> Self {
+        let measurer = StreamMeasurer::with_policy(policy);
+
+        Self {
+            layout: Layout::new(inner, max_width, Overflow::Clip, measurer),
+        }
+    }
+
+    /// Returns the display column on the current output line.
+    pub fn column(&self) -> usize {
+        self.layout.column
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.layout.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly bypasses width tracking.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.layout.inner()
+    }
+
+    /// Writes all buffered input and unwraps the underlying writer.
+    ///
+    /// An incomplete trailing UTF-8 sequence is written as U+FFFD. As with [`BufWriter::into_inner`](std::io::BufWriter::into_inner), the
+    /// underlying writer is not flushed, and on error the adapter is returned
+    /// in the [`IntoInnerError`].
+    #[allow(clippy::result_large_err)] // Mirrors `BufWriter::into_inner`.
+    pub fn into_inner(mut self) -> Result<W, IntoInnerError<Self>> {
+        match self.layout.finish() {
+            Ok(()) => Ok(self.layout.take_inner()),
+            Err(err) => Err(IntoInnerError(self, err)),
+        }
+    }
+}
+
+impl<W: Write> Write for TruncateWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.layout.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.layout.flush()
+    }
+}
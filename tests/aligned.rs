@@ -0,0 +1,48 @@
+//! Unit tests for [`render_aligned`].
+//!
+//! These tests verify that cursor moves follow only contested graphemes,
+//! that their columns account for the start column and control characters,
+//! and that the output measures the same as the input.
+
+use std::borrow::Cow;
+
+use runefix_core::*;
+
+#[test]
+#[cfg(feature = "emoji-zwj")]
+fn test_moves_follow_contested_graphemes() {
+    assert_eq!(render_aligned("a😀b🇯🇵c", 0), "a😀\x1b[4Gb🇯🇵\x1b[7Gc");
+    assert_eq!(render_aligned("±1", 0), "±\x1b[2G1");
+    assert_eq!(render_aligned("👩\u{200d}💻", 10), "👩\u{200d}💻\x1b[13G");
+}
+
+#[test]
+fn test_uncontested_lines_are_borrowed() {
+    for line in ["plain", "中文かな한글", "\x1b[1mbold\x1b[0m", ""] {
+        assert!(matches!(render_aligned(line, 3), Cow::Borrowed(_)));
+    }
+}
+
+#[test]
+fn test_columns_follow_controls_and_escapes() {
+    assert_eq!(
+        render_aligned("ab\t\x1b[31m😀\x1b[0m", 0),
+        "ab\t\x1b[31m😀\x1b[11G\x1b[0m"
+    );
+    assert_eq!(render_aligned("xyz\r😀", 5), "xyz\r😀\x1b[3G");
+
+    let sized = "\x1b]66;w=3;😀\x1b\\";
+    assert_eq!(
+        render_aligned(&format!("{sized}😀"), 0),
+        format!("{sized}😀\x1b[6G")
+    );
+}
+
+#[test]
+fn test_output_measures_the_same() {
+    let line = "\x1b[32m✔\x1b[0m 完了 👍🏽 ±1 🇯🇵 ok";
+    let out = render_aligned(line, 2);
+
+    assert_eq!(display_width_ansi(&out), display_width_ansi(line));
+    assert_eq!(strip_ansi(&out), strip_ansi(line));
+}
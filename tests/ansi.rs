@@ -0,0 +1,71 @@
+//! Unit tests for [`display_width_ansi`] and [`strip_ansi`].
+//!
+//! These tests verify that each ECMA-48 sequence type is recognized and
+//! assigned zero width, and that malformed or unterminated sequences are
+//! handled without consuming visible text.
+
+use std::borrow::Cow;
+
+use runefix_core::*;
+
+#[test]
+fn test_sgr() {
+    assert_eq!(display_width("\x1b[31mred\x1b[0m"), 10);
+    assert_eq!(display_width_ansi("\x1b[31mred\x1b[0m"), 3);
+    assert_eq!(display_width_ansi("\x1b[1;38;2;255;0;0m你好\x1b[m"), 4);
+    assert_eq!(strip_ansi("\x1b[31mred\x1b[0m"), "red");
+}
+
+#[test]
+fn test_csi_with_intermediates_and_private_parameters() {
+    assert_eq!(strip_ansi("a\x1b[?25lb\x1b[2 qc"), "abc");
+}
+
+#[test]
+fn test_osc() {
+    let link = "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\";
+    assert_eq!(display_width_ansi(link), 4);
+    assert_eq!(strip_ansi("\x1b]0;title\x07text"), "text");
+}
+
+#[test]
+fn test_control_strings() {
+    assert_eq!(strip_ansi("a\x1bPq#0;2;0;0;0\x1b\\b"), "ab"); // DCS
+    assert_eq!(strip_ansi("a\x1b_Gf=100;AAAA\x1b\\b"), "ab"); // APC
+    assert_eq!(strip_ansi("a\x1b^pm\x1b\\b\x1bXsos\x1b\\c"), "abc"); // PM, SOS
+}
+
+#[test]
+fn test_single_shift_and_other_escapes() {
+    assert_eq!(strip_ansi("a\x1bOPb"), "ab"); // SS3 F1
+    assert_eq!(strip_ansi("a\x1bN好b"), "ab"); // SS2 with a multi-byte character
+    assert_eq!(strip_ansi("\x1b7a\x1b8\x1b(Bb\x1bc"), "ab"); // DECSC, DECRC, G0 charset, RIS
+}
+
+#[test]
+fn test_c1_introducers() {
+    assert_eq!(strip_ansi("\u{9b}31mred\u{9b}0m"), "red");
+    assert_eq!(strip_ansi("\u{9d}0;title\u{9c}text"), "text");
+}
+
+#[test]
+fn test_malformed_and_unterminated() {
+    // A CSI interrupted by a non-CSI byte ends before it.
+    assert_eq!(strip_ansi("\x1b[31你好"), "你好");
+    // An ESC inside a control string aborts it.
+    assert_eq!(strip_ansi("\x1b]0;title\x1b[1mok"), "ok");
+    // Unterminated sequences extend to the end.
+    assert_eq!(strip_ansi("ok\x1b]8;;https://"), "ok");
+    assert_eq!(strip_ansi("ok\x1b["), "ok");
+    assert_eq!(display_width_ansi("ok\x1b"), 2);
+}
+
+#[test]
+fn test_plain_text_is_borrowed() {
+    assert!(matches!(strip_ansi("plain 文本"), Cow::Borrowed(_)));
+    assert!(matches!(strip_ansi(""), Cow::Borrowed(_)));
+    assert_eq!(
+        display_width_ansi("Hello 👋 世界"),
+        display_width("Hello 👋 世界")
+    );
+}
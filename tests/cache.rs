@@ -0,0 +1,119 @@
+//! Unit tests for [`WidthCache`].
+//!
+//! These tests verify that cached widths match uncached measurement, that the
+//! LRU stays bounded, and that statistics are counted correctly.
+
+use std::sync::Arc;
+use std::thread;
+
+use runefix_core::*;
+
+const SAMPLES: &[&str] = &[
+    "",
+    "ascii only",
+    "你a1👇",
+    "Hello 👋 世界！",
+    "👩\u{200d}💻 한국어 カタカナ",
+];
+
+#[test]
+fn test_matches_display_width() {
+    let cache = WidthCache::new(16);
+    for _ in 0..2 {
+        for s in SAMPLES {
+            assert_eq!(cache.display_width(s), display_width(s), "{s:?}");
+        }
+    }
+}
+
+#[test]
+fn test_hit_and_miss_counts() {
+    let cache = WidthCache::new(16);
+
+    // Grapheme widths are cached per thread, so start from a fresh one.
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            cache.display_width("世界世界");
+            cache.display_width("世界世界");
+        });
+    });
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!((stats.grapheme_hits, stats.grapheme_misses), (2, 2));
+    assert_eq!(stats.len, 1);
+    assert_eq!(stats.hit_rate(), 0.5);
+
+    cache.reset_stats();
+    assert_eq!(cache.stats().hits, 0);
+    assert_eq!(cache.stats().len, 1);
+}
+
+#[test]
+fn test_lru_eviction() {
+    let cache = WidthCache::new(2);
+    cache.display_width("a");
+    cache.display_width("b");
+    cache.display_width("a"); // `b` is now least recently used
+    cache.display_width("c");
+    assert_eq!(cache.stats().len, 2);
+
+    cache.reset_stats();
+    cache.display_width("a");
+    cache.display_width("b");
+    assert_eq!((cache.stats().hits, cache.stats().misses), (1, 1));
+}
+
+#[test]
+fn test_large_cache_stays_bounded() {
+    let cache = WidthCache::new(1000);
+    for i in 0..5000 {
+        let s = format!("行 {i}");
+        assert_eq!(cache.display_width(&s), display_width(&s));
+    }
+    assert_eq!(cache.stats().len, 1000);
+    assert_eq!(cache.display_width("行 4999"), 7);
+    assert_eq!(cache.stats().hits, 1);
+}
+
+#[test]
+fn test_zero_capacity_never_caches() {
+    let cache = WidthCache::new(0);
+    cache.display_width("你好");
+    cache.display_width("你好");
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.len), (0, 2, 0));
+}
+
+#[test]
+fn test_clear() {
+    let cache = WidthCache::new(4);
+    cache.display_width("你好");
+    cache.clear();
+    assert_eq!(cache.stats().len, 0);
+}
+
+#[test]
+fn test_shared_across_threads() {
+    let cache = Arc::new(WidthCache::new(64));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = Arc::clone(&cache);
+            thread::spawn(move || {
+                for s in SAMPLES {
+                    assert_eq!(cache.display_width(s), display_width(s));
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let stats = cache.stats();
+    assert_eq!(stats.hits + stats.misses, 4 * SAMPLES.len() as u64);
+    // Threads may race to measure the same string, but it is stored once.
+    assert!(stats.misses >= SAMPLES.len() as u64);
+    assert_eq!(stats.len, SAMPLES.len());
+}
@@ -0,0 +1,45 @@
+//! Unit tests for the [`columns`] formatting adapter.
+//!
+//! These tests verify that std format width, precision, fill, and
+//! alignment are interpreted in display columns rather than chars.
+
+use runefix_core::columns;
+
+#[test]
+fn test_plain_display() {
+    assert_eq!(format!("{}", columns("你a1👇")), "你a1👇");
+}
+
+#[test]
+fn test_width_alignment() {
+    assert_eq!(format!("{:<8}|", columns("你a1👇")), "你a1👇  |");
+    assert_eq!(format!("{:>8}|", columns("你a1👇")), "  你a1👇|");
+    assert_eq!(format!("{:^9}|", columns("你a1👇")), " 你a1👇  |");
+}
+
+#[test]
+fn test_default_alignment_is_left() {
+    assert_eq!(format!("{:6}|", columns("世界")), "世界  |");
+}
+
+#[test]
+fn test_precision_truncates_by_columns() {
+    assert_eq!(format!("{:.4}|", columns("你a1👇")), "你a1|");
+    // "你" does not fit in the remaining column, so width pads it back
+    assert_eq!(format!("{:5.2}|", columns("a你好")), "a    |");
+}
+
+#[test]
+fn test_custom_and_wide_fill() {
+    assert_eq!(format!("{:*>6}", columns("世界")), "**世界");
+    assert_eq!(format!("{:・<8}", columns("ab")), "ab・・・");
+}
+
+#[test]
+fn test_dynamic_width() {
+    let w = 10;
+    assert_eq!(
+        format!("{:>w$.p$}|", columns("Hello, 世界"), p = 8),
+        "   Hello, |"
+    );
+}
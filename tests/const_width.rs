@@ -0,0 +1,137 @@
+//! Unit tests for [`char_width`], [`display_width_const`], and [`width!`].
+//!
+//! These tests verify that compile-time widths agree with runtime measurement
+//! and that the macro can be used in const contexts.
+
+use runefix_core::*;
+
+const SAMPLES: &[&str] = &[
+    "",
+    "ascii only",
+    "見出し",
+    "名前 | 年齢 | 住所",
+    "Hello 👋 世界！",
+    "한국어 カタカナ ｶﾀｶﾅ",
+    "ＡＢＣ、。「」",
+    "café e\u{301}",
+    "が か\u{3099}",
+    "❤️ ©️ ↔️ ☺",
+    "👍🏽 👩\u{200d}💻 👨\u{200d}👩\u{200d}👧\u{200d}👦",
+    "🇯🇵🇺🇸 🇯",
+    "🏴\u{e0067}\u{e0062}\u{e0065}\u{e006e}\u{e0067}\u{e007f}",
+    "line\r\nbreak\ttab",
+    "a\u{483} \u{5d0}\u{5b0} \u{600}a",
+    "\u{200d}👩 a\u{200d}👩",
+    "क्षि \u{e01}\u{e33} \u{1100}\u{1161}\u{11a8}",
+];
+
+/// Code points covering every grapheme break class and conjunct property.
+const ALPHABET: &[char] = &[
+    'a',
+    ' ',
+    '\t',
+    '\r',
+    '\n',
+    '\u{7f}',
+    '\u{85}',
+    '\u{ad}',
+    '\u{200b}',
+    '\u{a9}',
+    '\u{301}',
+    '\u{483}',
+    '\u{5b0}',
+    '\u{600}',
+    '\u{610}',
+    '\u{e31}',
+    '\u{e33}',
+    '\u{915}',
+    '\u{937}',
+    '\u{93c}',
+    '\u{93f}',
+    '\u{94d}',
+    '\u{1100}',
+    '\u{1161}',
+    '\u{11a8}',
+    '가',
+    '각',
+    '\u{200c}',
+    '\u{200d}',
+    '\u{fe0f}',
+    '\u{20e3}',
+    '❤',
+    '👩',
+    '💻',
+    '👍',
+    '\u{1f3fd}',
+    '🇯',
+    '🇵',
+    '\u{e0067}',
+    '世',
+    'カ',
+    '\u{3099}',
+    'Ａ',
+    '。',
+];
+
+#[test]
+fn test_char_width_matches_rune_width() {
+    for c in (0..=0x10FFFF).filter_map(char::from_u32) {
+        assert_eq!(char_width(c) as usize, c.rune_width(), "{c:?}");
+    }
+}
+
+#[test]
+fn test_display_width_const_matches_display_width() {
+    for s in SAMPLES {
+        assert_eq!(display_width_const(s), display_width(s), "{s:?}");
+    }
+}
+
+#[test]
+fn test_display_width_const_matches_random_text() {
+    // xorshift64, seeded for reproducible failures.
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for _ in 0..20_000 {
+        let len = next() % 8;
+        let s: String = (0..len)
+            .filter_map(|_| match next() % 8 {
+                // Occasionally any scalar value, to cover classes not listed.
+                0 => char::from_u32((next() % 0x11_0000) as u32),
+                _ => Some(ALPHABET[next() as usize % ALPHABET.len()]),
+            })
+            .collect();
+
+        assert_eq!(display_width_const(&s), display_width(&s), "{s:?}");
+    }
+}
+
+#[test]
+fn test_controls_are_not_extended() {
+    // Control characters, including DEL and C1, are always their own cluster.
+    for c in (0..0x20).chain(0x7F..0xA0).filter_map(char::from_u32) {
+        for mark in ['\u{301}', '\u{3099}', '\u{fe0f}', '\u{1f3fd}'] {
+            let s = format!("{c}{mark}x");
+            assert_eq!(display_width_const(&s), display_width(&s), "{s:?}");
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "emoji-zwj")]
+fn test_width_macro_in_const_contexts() {
+    const HEADER: usize = width!("見出し");
+    const COLUMNS: [usize; 2] = [width!("名前"), width!("👩\u{200d}💻 ok")];
+
+    let rule = ['─'; width!("見出し")];
+
+    assert_eq!(HEADER, 6);
+    assert_eq!(COLUMNS, [4, 5]);
+    assert_eq!(rule.len(), HEADER);
+}
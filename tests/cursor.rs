@@ -0,0 +1,113 @@
+//! Unit tests for [`CursorSim`].
+//!
+//! These tests verify cursor movement for printable graphemes and control
+//! characters, pending-wrap behavior at the right margin, and early wrapping
+//! of wide graphemes at the last column.
+
+use runefix_core::*;
+
+fn simulate(columns: usize, start_col: usize, s: &str) -> ((usize, usize), usize) {
+    let mut sim = CursorSim::new(columns).with_start_column(start_col);
+    sim.print(s);
+    (sim.position(), sim.rows())
+}
+
+#[test]
+#[cfg(feature = "emoji-zwj")]
+fn test_printable_graphemes() {
+    assert_eq!(simulate(80, 0, "hello"), ((0, 5), 1));
+    assert_eq!(simulate(80, 2, "你好👩\u{200d}💻"), ((0, 8), 1));
+    assert_eq!(simulate(80, 0, "e\u{301}"), ((0, 1), 1));
+}
+
+#[test]
+fn test_pending_wrap() {
+    let mut sim = CursorSim::new(5);
+    sim.print("abcde");
+    assert_eq!(sim.position(), (0, 4));
+    assert!(sim.is_wrap_pending());
+    assert_eq!(sim.rows(), 1);
+
+    // Zero-width input does not trigger the deferred wrap.
+    sim.print("\x1b[0m\x07");
+    assert!(sim.is_wrap_pending());
+
+    sim.print("f");
+    assert_eq!(sim.position(), (1, 1));
+    assert!(!sim.is_wrap_pending());
+    assert_eq!(sim.rows(), 2);
+}
+
+#[test]
+fn test_wide_grapheme_at_last_column() {
+    // Column 4 is left blank; 中 starts the next row.
+    assert_eq!(simulate(5, 0, "abcd中"), ((1, 2), 2));
+    assert_eq!(simulate(5, 3, "中"), ((0, 4), 1));
+    assert_eq!(simulate(5, 4, "中"), ((1, 2), 2));
+
+    // A grapheme wider than the terminal is placed at column 0 anyway.
+    assert_eq!(simulate(1, 0, "中中"), ((1, 0), 2));
+}
+
+#[test]
+fn test_carriage_return_and_newline() {
+    assert_eq!(simulate(10, 4, "ab\rc"), ((0, 1), 1));
+    assert_eq!(simulate(10, 4, "ab\ncd"), ((1, 2), 2));
+    assert_eq!(simulate(10, 4, "ab\r\ncd"), ((1, 2), 2));
+
+    // `\r` cancels a pending wrap.
+    assert_eq!(simulate(3, 0, "abc\rx"), ((0, 1), 1));
+    // `\n` after a full row moves one row, not two.
+    assert_eq!(simulate(3, 0, "abc\nx"), ((1, 1), 2));
+
+    let mut raw = CursorSim::new(10).with_onlcr(false);
+    raw.print("ab\ncd");
+    assert_eq!(raw.position(), (1, 4));
+}
+
+#[test]
+fn test_backspace() {
+    assert_eq!(simulate(10, 0, "ab\x08"), ((0, 1), 1));
+    assert_eq!(simulate(10, 0, "\x08\x08"), ((0, 0), 1));
+
+    // From a pending wrap, `\b` moves left of the last column.
+    assert_eq!(simulate(4, 0, "abcd\x08"), ((0, 2), 1));
+    assert_eq!(simulate(4, 0, "abcd\x08x"), ((0, 3), 1));
+}
+
+#[test]
+fn test_tab() {
+    assert_eq!(simulate(80, 0, "\t"), ((0, 8), 1));
+    assert_eq!(simulate(80, 0, "abc\tx"), ((0, 9), 1));
+    assert_eq!(simulate(80, 8, "\t"), ((0, 16), 1));
+    assert_eq!(simulate(10, 0, "\t\t"), ((0, 9), 1));
+
+    let mut sim = CursorSim::new(80).with_tab_width(4);
+    sim.print("ab\t");
+    assert_eq!(sim.column(), 4);
+
+    // A tab at a pending wrap stays put.
+    let mut sim = CursorSim::new(3);
+    sim.print("abc\t");
+    assert_eq!(sim.position(), (0, 2));
+    assert!(sim.is_wrap_pending());
+}
+
+#[test]
+fn test_escape_sequences_are_zero_width() {
+    assert_eq!(
+        simulate(6, 0, "\x1b[31m日本\x1b[0m\x1b]8;;x\x1b\\語\x1b]8;;\x1b\\"),
+        ((0, 5), 1)
+    );
+}
+
+#[test]
+fn test_start_column_and_incremental_printing() {
+    let mut sim = CursorSim::new(8).with_start_column(100);
+    assert_eq!(sim.position(), (0, 7));
+
+    sim.print("a").print("bc");
+    assert_eq!(sim.position(), (1, 2));
+    assert_eq!(sim.row(), 1);
+    assert_eq!(sim.columns(), 8);
+}
@@ -0,0 +1,50 @@
+//! Unit tests for the per-dataset cargo features.
+//!
+//! These tests verify that each dataset feature controls the width of the
+//! characters it covers, and that always-included data is unaffected.
+//! Run with `--no-default-features --features std` to check the opt-outs.
+
+use runefix_core::*;
+
+#[test]
+fn test_core_datasets_always_included() {
+    assert_eq!(display_width("中文"), 4);
+    assert_eq!(display_width("，。"), 4);
+    assert_eq!(display_width("ＡＢ"), 4);
+    assert_eq!(display_width("😀"), 2);
+    assert_eq!(display_width("❤️"), 2);
+    assert_eq!(display_width("👍🏽"), 2);
+    assert_eq!(display_width("🇯🇵"), 2);
+}
+
+#[test]
+fn test_hangul_feature() {
+    // Without the dataset, the Hangul Syllables block is still wide.
+    assert_eq!(display_width("한"), 2);
+    assert_eq!(char_width('한'), 2);
+    assert_eq!(display_width("\u{d7a3}"), 2);
+}
+
+#[test]
+fn test_kana_feature() {
+    // Without the dataset, the kana blocks are still wide.
+    assert_eq!(display_width("カ"), 2);
+    assert_eq!(char_width('ぁ'), 2);
+    assert_eq!(display_width("\u{1b001}"), 2); // Kana Supplement
+}
+
+#[test]
+fn test_emoji_zwj_feature() {
+    let expected = if cfg!(feature = "emoji-zwj") { 2 } else { 1 };
+    assert_eq!(display_width("👩\u{200d}💻"), expected);
+    assert_eq!(display_width_const("👩\u{200d}💻"), expected);
+}
+
+#[test]
+fn test_cjk_ext_feature() {
+    // Without the dataset, the extension blocks are still wide.
+    assert_eq!(display_width("㐀"), 2); // Extension A
+    assert_eq!(display_width("𠀀"), 2); // Extension B
+    assert_eq!(char_width('\u{30000}'), 2); // Extension G
+    assert_eq!(display_width("丽"), 2); // Compatibility Ideograph
+}
@@ -0,0 +1,96 @@
+//! Unit tests for [`render_html`], [`render_svg`], and the [`CellGrid`] exports.
+//!
+//! These tests verify that every cell is a fixed-width box, that wide
+//! graphemes span their full width, and that markup characters are escaped.
+
+use runefix_core::*;
+
+#[test]
+fn test_render_html() {
+    assert_eq!(
+        render_html("a中\n<&>"),
+        concat!(
+            "<div class=\"runefix-grid\">\n",
+            "<style>.runefix-grid{font-family:monospace;line-height:1.25;white-space:pre}",
+            ".runefix-grid span{display:inline-block;text-align:center;overflow:visible}",
+            ".runefix-grid .w1{width:1ch}.runefix-grid .w2{width:2ch}</style>\n",
+            "<div><span class=\"w1\">a</span><span class=\"w2\">中</span></div>\n",
+            "<div><span class=\"w1\">&lt;</span><span class=\"w1\">&amp;</span>",
+            "<span class=\"w1\">&gt;</span></div>\n",
+            "</div>\n",
+        )
+    );
+}
+
+#[test]
+fn test_render_svg() {
+    assert_eq!(
+        render_svg("😀x\n\"'"),
+        concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"40\" ",
+            "viewBox=\"0 0 30 40\" font-family=\"monospace\" font-size=\"16\">\n",
+            "<rect width=\"30\" height=\"40\" fill=\"#fff\"/>\n",
+            "<g fill=\"none\" stroke=\"#ccc\">\n",
+            "<rect x=\"0\" y=\"0\" width=\"20\" height=\"20\"/>\n",
+            "<rect x=\"20\" y=\"0\" width=\"10\" height=\"20\"/>\n",
+            "<rect x=\"0\" y=\"20\" width=\"10\" height=\"20\"/>\n",
+            "<rect x=\"10\" y=\"20\" width=\"10\" height=\"20\"/>\n",
+            "<rect x=\"20\" y=\"20\" width=\"10\" height=\"20\"/>\n",
+            "</g>\n",
+            "<g text-anchor=\"middle\" fill=\"#000\">\n",
+            "<text x=\"10\" y=\"15\">😀</text>\n",
+            "<text x=\"25\" y=\"15\">x</text>\n",
+            "<text x=\"5\" y=\"35\">&quot;</text>\n",
+            "<text x=\"15\" y=\"35\">&#39;</text>\n",
+            "</g>\n</svg>\n",
+        )
+    );
+}
+
+#[test]
+fn test_text_layout_matches_cell_grid() {
+    let text = "\x1b[31m表格\x1b[0m|\nab\tc";
+
+    let mut grid = CellGrid::new(2, 9);
+    grid.write_at(0, 0, "\x1b[31m表格\x1b[0m|");
+    grid.write_at(1, 0, "ab\tc");
+
+    assert_eq!(render_html(text), grid.to_html());
+    assert_eq!(render_svg(text), grid.to_svg());
+}
+
+#[test]
+fn test_overwritten_text_keeps_full_width() {
+    // `\r` and `\b` move back over printed cells without shrinking the line.
+    for (text, columns, line) in [
+        ("abcdef\rX", 6, "Xbcdef"),
+        ("中文\rab!", 4, "ab! "),
+        ("ab\x08\x08Xyz", 3, "Xyz"),
+    ] {
+        let mut grid = CellGrid::new(1, columns);
+        grid.print(text);
+        assert_eq!(grid.lines(), [line]);
+
+        assert_eq!(render_html(text), grid.to_html());
+        assert_eq!(render_svg(text), grid.to_svg());
+    }
+}
+
+#[test]
+fn test_grid_spans_follow_cell_widths() {
+    let mut grid = CellGrid::new(1, 4);
+    grid.print("🇯🇵a");
+
+    let html = grid.to_html();
+    assert!(html.contains("<div><span class=\"w2\">🇯🇵</span><span class=\"w1\">a</span><span class=\"w1\"> </span></div>"));
+
+    let svg = grid.to_svg();
+    assert_eq!(svg.matches("<rect x=").count(), 3);
+    assert_eq!(svg.matches("<text ").count(), 2);
+}
+
+#[test]
+fn test_empty_text() {
+    assert!(render_html("").contains("<div><span class=\"w1\"> </span></div>"));
+    assert!(render_svg("").contains("width=\"10\" height=\"20\""));
+}
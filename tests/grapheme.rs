@@ -47,3 +47,20 @@ fn test_split_by_width() {
     let parts = split_by_width(s, 4);
     assert_eq!(parts, vec!["你a1", "👇"]); // Splits before overflowing
 }
+
+#[test]
+fn test_wrap_lines() {
+    let s = "你a1👇\n\nab"; // Hard line breaks reset the column
+    assert_eq!(wrap_lines(s, 4), vec!["你a1", "👇", "", "ab"]);
+    assert_eq!(wrap_lines("", 4), vec![""]);
+}
+
+#[test]
+fn test_wrap_lines_crlf() {
+    assert_eq!(
+        wrap_lines("你好世界\r\n\r\nab\r\n", 4),
+        vec!["你好", "世界", "", "ab", ""]
+    );
+    // A `\r` not followed by `\n` is not a line break.
+    assert_eq!(wrap_lines("a\rb\r", 4), vec!["a\rb\r"]);
+}
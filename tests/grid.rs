@@ -0,0 +1,118 @@
+//! Unit tests for [`CellGrid`].
+//!
+//! These tests verify lead and continuation cells for wide graphemes,
+//! overwriting halves of wide graphemes, wrapping, scrolling, clearing,
+//! and rendering back to strings.
+
+use runefix_core::*;
+
+#[cfg(feature = "emoji-zwj")]
+fn lead(grapheme: &str, width: usize) -> Cell {
+    Cell::Lead {
+        grapheme: grapheme.to_string(),
+        width,
+    }
+}
+
+#[test]
+#[cfg(feature = "emoji-zwj")]
+fn test_wide_graphemes_use_continuation_cells() {
+    let mut grid = CellGrid::new(1, 8);
+    grid.print("a中👩\u{200d}💻e\u{301}");
+
+    assert_eq!(grid.cell(0, 0), Some(&lead("a", 1)));
+    assert_eq!(grid.cell(0, 1), Some(&lead("中", 2)));
+    assert_eq!(grid.cell(0, 2), Some(&Cell::Continuation));
+    assert_eq!(grid.cell(0, 3), Some(&lead("👩\u{200d}💻", 2)));
+    assert_eq!(grid.cell(0, 4), Some(&Cell::Continuation));
+    assert_eq!(grid.cell(0, 5), Some(&lead("e\u{301}", 1)));
+    assert_eq!(grid.cell(0, 6), Some(&Cell::Blank));
+    assert_eq!(grid.cell(0, 8), None);
+    assert_eq!(grid.cell(1, 0), None);
+    assert_eq!(grid.line(0).unwrap(), "a中👩\u{200d}💻e\u{301}  ");
+}
+
+#[test]
+fn test_overwriting_half_of_wide_grapheme() {
+    let mut grid = CellGrid::new(1, 6);
+    grid.print("中文字");
+
+    // Left half of 文.
+    grid.write_at(0, 2, "x");
+    assert_eq!(grid.lines(), ["中x 字"]);
+
+    // A wide grapheme straddling 中 and the blank cell.
+    grid.write_at(0, 1, "日");
+    assert_eq!(grid.lines(), [" 日 字"]);
+    assert_eq!(grid.cell(0, 0), Some(&Cell::Blank));
+    assert_eq!(grid.cell(0, 3), Some(&Cell::Blank));
+
+    // Right half of 字.
+    grid.write_at(0, 5, "y");
+    assert_eq!(grid.lines(), [" 日  y"]);
+    assert_eq!(grid.cell(0, 4), Some(&Cell::Blank));
+}
+
+#[test]
+fn test_wrapping_and_early_wrap() {
+    let mut grid = CellGrid::new(3, 5);
+    grid.print("abcd中ef");
+
+    assert_eq!(grid.lines(), ["abcd ", "中ef ", "     "]);
+    assert_eq!(grid.cursor(), (1, 4));
+}
+
+#[test]
+fn test_printing_past_bottom_scrolls() {
+    let mut grid = CellGrid::new(2, 4);
+    grid.print("one\ntwo\nthre");
+
+    assert_eq!(grid.lines(), ["two ", "thre"]);
+    assert_eq!(grid.cursor(), (1, 3));
+
+    // The pending wrap scrolls once more.
+    grid.print("e");
+    assert_eq!(grid.lines(), ["thre", "e   "]);
+    assert_eq!(grid.cursor(), (1, 1));
+}
+
+#[test]
+fn test_scroll_and_clear() {
+    let mut grid = CellGrid::new(3, 2);
+    grid.print("a\r\nb\r\nc");
+
+    grid.scroll_down(1);
+    assert_eq!(grid.lines(), ["  ", "a ", "b "]);
+
+    grid.scroll_up(2);
+    assert_eq!(grid.lines(), ["b ", "  ", "  "]);
+
+    grid.scroll_up(10);
+    assert_eq!(grid.lines(), ["  ", "  ", "  "]);
+
+    grid.write_at(1, 0, "xy");
+    grid.clear_row(0);
+    assert_eq!(grid.to_string(), "  \nxy\n  ");
+
+    grid.clear();
+    assert_eq!(grid, CellGrid::new(3, 2));
+}
+
+#[test]
+fn test_controls_and_escapes() {
+    let mut grid = CellGrid::new(1, 10);
+    grid.print("\x1b[1mab\x1b[0m\tc\x08d\rX");
+
+    assert_eq!(grid.lines(), ["Xb      d "]);
+    assert_eq!(grid.cursor(), (0, 1));
+}
+
+#[test]
+fn test_move_to_is_clamped() {
+    let mut grid = CellGrid::new(2, 3);
+    grid.write_at(9, 9, "z");
+
+    assert_eq!(grid.lines(), ["   ", "  z"]);
+    assert_eq!(grid.rows(), 2);
+    assert_eq!(grid.columns(), 3);
+}
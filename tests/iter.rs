@@ -0,0 +1,58 @@
+//! Unit tests for the lazy grapheme iterators.
+//!
+//! These tests verify that [`grapheme_widths_iter`] and [`wrap_iter`]
+//! agree with their `Vec`-returning counterparts.
+
+use runefix_core::*;
+
+#[test]
+fn test_grapheme_widths_iter() {
+    let items: Vec<_> = grapheme_widths_iter("你a1👇").collect();
+    assert_eq!(
+        items,
+        vec![
+            (0, "你", 2, 0),
+            (3, "a", 1, 2),
+            (4, "1", 1, 3),
+            (5, "👇", 2, 4)
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "emoji-zwj")]
+fn test_grapheme_widths_iter_zwj() {
+    let items: Vec<_> = grapheme_widths_iter("a👩‍💻b").collect();
+    assert_eq!(
+        items,
+        vec![(0, "a", 1, 0), (1, "👩‍💻", 2, 1), (12, "b", 1, 3)]
+    );
+}
+
+#[test]
+fn test_wrap_iter_borrows() {
+    let s = "你a1👇";
+    let lines: Vec<&str> = wrap_iter(s, 4).collect();
+    assert_eq!(lines, vec!["你a1", "👇"]);
+    assert_eq!(lines[1].as_ptr(), s[5..].as_ptr());
+}
+
+#[test]
+fn test_wrap_iter_too_narrow() {
+    // Each line takes at least one grapheme, even if wider than max_width
+    let lines: Vec<&str> = wrap_iter("你好", 1).collect();
+    assert_eq!(lines, vec!["你", "好"]);
+}
+
+#[test]
+fn test_wrap_iter_matches_split_by_width() {
+    let s = "Hello 👋 世界！こんにちは, 안녕하세요";
+    for w in 0..12 {
+        assert_eq!(wrap_iter(s, w).collect::<Vec<_>>(), split_by_width(s, w));
+    }
+}
+
+#[test]
+fn test_wrap_iter_empty() {
+    assert_eq!(wrap_iter("", 5).next(), None);
+}
@@ -0,0 +1,177 @@
+//! Unit tests for [`encode_text_sizing`] and kitty text-sizing (OSC 66) sequences.
+//!
+//! These tests verify which graphemes are wrapped in each [`TextSizingMode`],
+//! idempotency, and that encoded strings measure the same as their input with
+//! the ANSI-aware functions.
+
+use std::borrow::Cow;
+
+use runefix_core::*;
+
+fn sized(w: usize, text: &str) -> String {
+    format!("\x1b]66;w={w};{text}\x1b\\")
+}
+
+#[test]
+#[cfg(feature = "emoji-zwj")]
+fn test_contested_graphemes_are_wrapped() {
+    let flag = "🇯🇵";
+    let zwj = "👩\u{200d}💻";
+    let keycap = "1\u{fe0f}\u{20e3}";
+
+    let encoded = encode_text_sizing(
+        "a中🇯🇵👩\u{200d}💻1\u{fe0f}\u{20e3}×\u{e0b0}",
+        TextSizingMode::Contested,
+    );
+    assert_eq!(
+        encoded,
+        format!(
+            "a中{}{}{}{}{}",
+            sized(2, flag),
+            sized(2, zwj),
+            sized(2, keycap),
+            sized(1, "×"),
+            sized(1, "\u{e0b0}")
+        )
+    );
+}
+
+#[test]
+fn test_uncontested_text_is_borrowed() {
+    for s in ["plain ascii", "中文かな한글", "\x1b[31mred\x1b[0m\n", ""] {
+        assert!(matches!(
+            encode_text_sizing(s, TextSizingMode::Contested),
+            Cow::Borrowed(_)
+        ));
+    }
+}
+
+#[test]
+fn test_all_mode_wraps_every_printable_grapheme() {
+    assert_eq!(
+        encode_text_sizing("a中\t\x1b[1m😀\x1b[0m\n", TextSizingMode::All),
+        format!(
+            "{}{}\t\x1b[1m{}\x1b[0m\n",
+            sized(1, "a"),
+            sized(2, "中"),
+            sized(2, "😀")
+        )
+    );
+    assert_eq!(TextSizingMode::default(), TextSizingMode::Contested);
+}
+
+#[test]
+fn test_ambiguous_table_matches_asset() {
+    let asset = include_str!("../src/assets/east_asian_ambiguous.json");
+    assert!(asset.contains("\"unicode_version\": \""));
+
+    // Each line of the asset's range list reads `["00A1", "00A1"],`.
+    let ranges: Vec<(u32, u32)> = asset
+        .lines()
+        .filter_map(|line| {
+            let mut hex = line.split('"').skip(1).step_by(2);
+            let lo = u32::from_str_radix(hex.next()?, 16).ok()?;
+            let hi = u32::from_str_radix(hex.next()?, 16).ok()?;
+            Some((lo, hi))
+        })
+        .collect();
+    assert!(ranges.len() > 100);
+
+    let mut checked = 0;
+    for cp in ranges.iter().flat_map(|&(lo, hi)| [lo, hi]) {
+        let c = char::from_u32(cp).unwrap().to_string();
+
+        // Combining marks and variation selectors are zero-width, never wrapped.
+        if display_width(&c) > 0 {
+            let encoded = encode_text_sizing(&c, TextSizingMode::Contested);
+            assert_eq!(encoded, sized(display_width(&c), &c), "U+{cp:04X}");
+            checked += 1;
+        }
+    }
+    assert!(checked > 300);
+
+    // Neighbours of ambiguous characters outside the table stay unwrapped.
+    for c in ['a', 'ä', 'ñ', '╌'] {
+        let cp = c as u32;
+        assert!(!ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&cp)));
+        assert_eq!(
+            encode_text_sizing(&c.to_string(), TextSizingMode::Contested),
+            c.to_string()
+        );
+    }
+}
+
+#[test]
+fn test_encoding_is_idempotent() {
+    let once = encode_text_sizing("🇯🇵 ok ×", TextSizingMode::All);
+    assert!(matches!(
+        encode_text_sizing(&once, TextSizingMode::All),
+        Cow::Borrowed(_)
+    ));
+}
+
+#[test]
+fn test_encoded_strings_measure_the_same() {
+    let s = "\x1b[32m✔\x1b[0m 進捗 👍🏽 ±1 🇯🇵";
+
+    for mode in [TextSizingMode::Contested, TextSizingMode::All] {
+        let encoded = encode_text_sizing(s, mode);
+
+        assert_eq!(display_width_ansi(&encoded), display_width_ansi(s));
+        assert_eq!(strip_ansi(&encoded), strip_ansi(s));
+        assert_eq!(prompt_width(&encoded, Shell::Zsh), display_width_ansi(s));
+
+        // The backslash of each ST terminator is doubled for bash.
+        let marked = mark_prompt_escapes(&encoded, Shell::Bash);
+        assert!(marked.ends_with("🇯🇵\\[\x1b\\\\\\]"), "{marked:?}");
+        assert_eq!(prompt_width(&marked, Shell::Bash), display_width_ansi(s));
+
+        let mut sim = CursorSim::new(80);
+        sim.print(&encoded);
+        assert_eq!(sim.column(), display_width_ansi(s));
+    }
+}
+
+#[test]
+fn test_truncate_and_wrap_keep_sequences_whole() {
+    let encoded = encode_text_sizing("ab👍🏽cd", TextSizingMode::Contested);
+
+    assert_eq!(truncate_ansi(&encoded, 3), "ab");
+    assert_eq!(truncate_ansi(&encoded, 4), format!("ab{}", sized(2, "👍🏽")));
+    assert_eq!(
+        wrap_ansi(&encoded, 3),
+        vec![
+            "ab".to_string(),
+            format!("{}c", sized(2, "👍🏽")),
+            "d".to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_sequence_metadata() {
+    // Scale and explicit width.
+    assert_eq!(display_width_ansi("\x1b]66;s=2:w=3;abc\x07"), 6);
+    // Automatic width uses the text's own width.
+    assert_eq!(display_width_ansi("\x1b]66;s=2;中a\x1b\\"), 6);
+    assert_eq!(display_width_ansi("\x1b]66;;中\x1b\\"), 2);
+    // Malformed metadata makes it an ordinary, zero-width escape.
+    assert_eq!(display_width_ansi("\x1b]66;w=x;abc\x1b\\"), 0);
+    assert_eq!(display_width_ansi("\x1b]66;abc\x1b\\"), 0);
+}
+
+#[test]
+fn test_cell_grid_stores_sized_text() {
+    let mut grid = CellGrid::new(1, 6);
+    grid.print(&format!("a{}b", sized(3, "👍")));
+
+    assert_eq!(
+        grid.cell(0, 1),
+        Some(&Cell::Lead {
+            grapheme: "👍".to_string(),
+            width: 3
+        })
+    );
+    assert_eq!(grid.cell(0, 3), Some(&Cell::Continuation));
+    assert_eq!(grid.lines(), ["a👍b "]);
+}
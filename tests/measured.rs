@@ -0,0 +1,80 @@
+//! Unit tests for [`MeasuredStr`].
+//!
+//! These tests verify that every indexed query returns exactly what the
+//! corresponding free function computes by scanning the string.
+
+use runefix_core::*;
+
+const SAMPLES: &[&str] = &[
+    "",
+    "你a1👇",
+    "Hello 👋 世界！",
+    "e\u{301}x\u{0}y",
+    "👩‍💻 한국어 カタカナ",
+];
+
+#[test]
+fn test_width_and_count() {
+    let m = MeasuredStr::new("你a1👇");
+    assert_eq!(m.width(), 6);
+    assert_eq!(m.grapheme_count(), 4);
+    assert_eq!(m.as_str(), "你a1👇");
+}
+
+#[test]
+fn test_truncate_matches() {
+    for s in SAMPLES {
+        let m = MeasuredStr::new(s);
+        for w in 0..=m.width() + 1 {
+            assert_eq!(m.truncate(w), truncate_by_width(s, w), "{s:?} {w}");
+        }
+    }
+}
+
+#[test]
+fn test_slice_by_columns_matches() {
+    for s in SAMPLES {
+        let m = MeasuredStr::new(s);
+        for start in 0..=m.width() + 1 {
+            for end in 0..=m.width() + 2 {
+                assert_eq!(
+                    m.slice_by_columns(start, end),
+                    slice_by_columns(s, start, end),
+                    "{s:?} {start}..{end}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_column_mapping_matches() {
+    for s in SAMPLES {
+        let m = MeasuredStr::new(s);
+        for b in 0..=s.len() + 1 {
+            assert_eq!(m.column_of_byte(b), column_of_byte(s, b), "{s:?} {b}");
+            for e in 0..=s.len() + 1 {
+                assert_eq!(
+                    m.columns_of_byte_range(b..e),
+                    columns_of_byte_range(s, b..e)
+                );
+            }
+        }
+        for col in 0..=m.width() + 1 {
+            for bias in [Bias::Left, Bias::Right] {
+                assert_eq!(m.byte_at_column(col, bias), byte_at_column(s, col, bias));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_wrap_matches() {
+    for s in SAMPLES {
+        let m = MeasuredStr::new(s);
+        for w in 0..=m.width() + 1 {
+            let lines: Vec<&str> = m.wrap(w).collect();
+            assert_eq!(lines, wrap_iter(s, w).collect::<Vec<_>>(), "{s:?} {w}");
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Unit tests for width-aware padding and [`fit`].
+//!
+//! These tests verify padding by display columns (not `char` count),
+//! multi-column fill graphemes, dot leaders, and exact-width fitting.
+
+use runefix_core::{Align, fit, pad_center, pad_left, pad_right};
+use std::borrow::Cow;
+
+#[test]
+fn test_pad_left() {
+    assert_eq!(pad_left("你a1👇", 8, " "), "  你a1👇");
+}
+
+#[test]
+fn test_pad_right() {
+    assert_eq!(pad_right("你a1👇", 8, " "), "你a1👇  ");
+}
+
+#[test]
+fn test_pad_center_odd_padding() {
+    assert_eq!(pad_center("世界", 7, "*"), "*世界**");
+}
+
+#[test]
+fn test_dot_leaders() {
+    assert_eq!(pad_right("Chapter 1", 14, "."), "Chapter 1.....");
+    assert_eq!(pad_right("第一章", 14, " ."), "第一章 . . . .");
+}
+
+#[test]
+fn test_multi_column_fill() {
+    // "・" is 2 columns wide; the odd column is filled with a space next to the text
+    assert_eq!(pad_left("abc", 8, "・"), "・・ abc");
+}
+
+#[test]
+fn test_no_allocation_when_wide_enough() {
+    assert!(matches!(pad_right("你好", 4, " "), Cow::Borrowed("你好")));
+    assert!(matches!(fit("你好", 4, Align::Left), Cow::Borrowed("你好")));
+}
+
+#[test]
+fn test_fit_truncates_and_pads() {
+    assert_eq!(fit("你好世界", 5, Align::Left), "你好 ");
+    assert_eq!(fit("你好世界", 5, Align::Right), " 你好");
+    assert_eq!(fit("你好世界", 5, Align::Center), "你好 ");
+    assert_eq!(fit("ab", 5, Align::Center), " ab  ");
+}
+
+#[test]
+fn test_fit_borrows_exact_prefix() {
+    assert!(matches!(
+        fit("你好世界", 4, Align::Left),
+        Cow::Borrowed("你好")
+    ));
+    assert!(matches!(
+        fit("ab\u{301}cd", 2, Align::Right),
+        Cow::Borrowed("ab\u{301}")
+    ));
+    assert!(matches!(
+        fit("你好", 4, Align::Center),
+        Cow::Borrowed("你好")
+    ));
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "rayon")]
+
+//! Unit tests for the parallel batch APIs.
+//!
+//! These tests verify that parallel results are identical to the sequential
+//! functions, including documents large enough to be split into many chunks.
+
+use runefix_core::*;
+
+const SAMPLES: &[&str] = &[
+    "",
+    "ascii only",
+    "你a1👇",
+    "Hello 👋 世界！",
+    "👩\u{200d}💻 한국어 カタカナ",
+];
+
+fn large_document() -> String {
+    (0..5_000)
+        .map(|i| format!("{i}: {}\n", SAMPLES[i % SAMPLES.len()].repeat(i % 7)))
+        .collect()
+}
+
+#[test]
+fn test_display_widths_par() {
+    let expected: Vec<_> = SAMPLES.iter().map(|s| display_width(s)).collect();
+    assert_eq!(display_widths_par(SAMPLES), expected);
+
+    let owned: Vec<String> = SAMPLES.iter().map(|s| s.to_string()).collect();
+    assert_eq!(display_widths_par(&owned), expected);
+}
+
+#[test]
+fn test_max_column_widths_par() {
+    let rows = vec![
+        vec!["id", "name", "note"],
+        vec!["1", "你好世界", ""],
+        vec!["100", "👋"],
+        vec![],
+    ];
+    assert_eq!(max_column_widths_par(&rows), vec![3, 8, 4]);
+
+    let empty: Vec<Vec<&str>> = Vec::new();
+    assert!(max_column_widths_par(&empty).is_empty());
+}
+
+#[test]
+fn test_wrap_lines_matches_sequential() {
+    let doc = large_document();
+    for max_width in [1, 3, 10, 80] {
+        assert_eq!(wrap_lines_par(&doc, max_width), wrap_lines(&doc, max_width));
+    }
+}
+
+#[test]
+fn test_wrap_lines_crlf_matches_sequential() {
+    let doc = large_document().replace('\n', "\r\n");
+    assert_eq!(wrap_lines_par(&doc, 10), wrap_lines(&doc, 10));
+    assert!(
+        !wrap_lines_par(&doc, 10)
+            .iter()
+            .any(|line| line.contains('\r'))
+    );
+}
+
+#[test]
+fn test_wrap_lines_without_line_breaks() {
+    let doc = "你好".repeat(20_000);
+    assert_eq!(wrap_lines_par(&doc, 7), wrap_lines(&doc, 7));
+}
+
+#[test]
+fn test_wrap_lines_keeps_empty_lines() {
+    assert_eq!(wrap_lines("a\n\nb\n", 5), vec!["a", "", "b", ""]);
+    assert_eq!(wrap_lines_par("a\n\nb\n", 5), vec!["a", "", "b", ""]);
+}
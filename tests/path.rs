@@ -0,0 +1,43 @@
+//! Unit tests for [`truncate_path`].
+//!
+//! These tests verify that directory components are collapsed before the
+//! file name is touched, and that mixed ASCII / CJK paths stay aligned.
+
+use runefix_core::truncate_path;
+use std::ffi::OsStr;
+use std::path::Path;
+
+#[test]
+fn test_path_fits() {
+    assert_eq!(truncate_path("src/main.rs", 20), "src/main.rs");
+}
+
+#[test]
+fn test_collapse_middle_components() {
+    let path = "/home/user/dev/プロジェクト/main.rs"; // width = 35
+    assert_eq!(truncate_path(path, 30), "/home/…/プロジェクト/main.rs");
+    assert_eq!(truncate_path(path, 20), "/home/…/main.rs");
+}
+
+#[test]
+fn test_elide_all_directories() {
+    assert_eq!(truncate_path("/home/user/main.rs", 12), "…/main.rs");
+}
+
+#[test]
+fn test_truncate_inside_file_name_keeps_extension() {
+    let path = "docs/very_long_file_name.md";
+    assert_eq!(truncate_path(path, 12), "…/very….md");
+}
+
+#[test]
+fn test_truncate_file_name_only() {
+    assert_eq!(truncate_path("設計書ドキュメント.txt", 12), "設計書….txt");
+}
+
+#[test]
+fn test_path_and_os_str_input() {
+    let path = Path::new("/var/log/システム/app.log");
+    assert_eq!(truncate_path(path, 16), "/var/…/app.log");
+    assert_eq!(truncate_path(OsStr::new("a/b"), 3), "a/b");
+}
@@ -0,0 +1,63 @@
+//! Unit tests for byte offset ↔ display column mapping.
+//!
+//! These tests verify cursor placement, hit-testing with [`Bias`] inside
+//! wide graphemes, and highlight spans for byte ranges.
+
+use runefix_core::{Bias, byte_at_column, column_of_byte, columns_of_byte_range};
+
+// "你a1👇": 你 = bytes 0..3 / cols 0..2, a = 3 / 2, 1 = 4 / 3, 👇 = 5..9 / 4..6
+const TEXT: &str = "你a1👇";
+
+#[test]
+fn test_column_of_byte() {
+    assert_eq!(column_of_byte(TEXT, 0), 0);
+    assert_eq!(column_of_byte(TEXT, 3), 2);
+    assert_eq!(column_of_byte(TEXT, 5), 4);
+    assert_eq!(column_of_byte(TEXT, 100), 6);
+}
+
+#[test]
+fn test_column_of_byte_inside_cluster() {
+    assert_eq!(column_of_byte(TEXT, 1), 0);
+    assert_eq!(column_of_byte("e\u{301}x", 1), 0); // combining acute accent
+}
+
+#[test]
+fn test_byte_at_column_boundaries() {
+    assert_eq!(byte_at_column(TEXT, 0, Bias::Left), 0);
+    assert_eq!(byte_at_column(TEXT, 3, Bias::Right), 4);
+    assert_eq!(byte_at_column(TEXT, 6, Bias::Left), 9);
+    assert_eq!(byte_at_column(TEXT, 42, Bias::Right), 9);
+}
+
+#[test]
+fn test_byte_at_column_inside_wide_grapheme() {
+    assert_eq!(byte_at_column(TEXT, 5, Bias::Left), 5);
+    assert_eq!(byte_at_column(TEXT, 5, Bias::Right), 9);
+}
+
+#[test]
+fn test_columns_of_byte_range() {
+    assert_eq!(columns_of_byte_range(TEXT, 0..3), 0..2);
+    assert_eq!(columns_of_byte_range(TEXT, 4..6), 3..6);
+    assert_eq!(columns_of_byte_range(TEXT, 4..4), 3..3);
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_inverted_byte_range_is_empty_at_start() {
+    // `start > end` is an empty range, positioned at `start` like `4..4`.
+    assert_eq!(columns_of_byte_range(TEXT, 4..0), 3..3);
+    assert_eq!(columns_of_byte_range("a", 1..0), 1..1);
+}
+
+#[test]
+fn test_round_trip() {
+    for (byte, _) in TEXT.char_indices() {
+        let col = column_of_byte(TEXT, byte);
+        assert_eq!(
+            column_of_byte(TEXT, byte_at_column(TEXT, col, Bias::Left)),
+            col
+        );
+    }
+}
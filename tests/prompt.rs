@@ -0,0 +1,192 @@
+//! Unit tests for [`prompt_width`] and [`mark_prompt_escapes`].
+//!
+//! These tests verify marker handling for each [`Shell`], literal escapes,
+//! raw escape codes outside markers, and that marking is idempotent.
+
+use std::borrow::Cow;
+
+use runefix_core::*;
+
+#[test]
+fn test_bash_markers() {
+    assert_eq!(
+        prompt_width("\\[\x1b[32m\\]user@主机\\[\x1b[0m\\]:~$ ", Shell::Bash),
+        13
+    );
+    assert_eq!(
+        prompt_width("\x01\x1b[1m\x02ok\x01\x1b[0m\x02", Shell::Bash),
+        2
+    );
+    assert_eq!(prompt_width("a\\\\b", Shell::Bash), 3);
+}
+
+#[test]
+fn test_zsh_markers() {
+    assert_eq!(
+        prompt_width("%{\x1b[35m%}📁 文档%{\x1b[0m%} %% ", Shell::Zsh),
+        10
+    );
+    // Bash markers have no meaning in zsh.
+    assert_eq!(prompt_width("\\[x\\]", Shell::Zsh), 5);
+}
+
+#[test]
+fn test_readline_markers() {
+    assert_eq!(
+        prompt_width("\x01\x1b[31m\x02>>> \x01\x1b[0m\x02", Shell::Readline),
+        4
+    );
+    assert_eq!(prompt_width("%{x%}", Shell::Readline), 5);
+}
+
+#[test]
+fn test_raw_escapes_are_zero_width() {
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Readline] {
+        assert_eq!(prompt_width("\x1b[1;34m~/项目\x1b[0m ❯ ", shell), 9);
+    }
+}
+
+#[test]
+fn test_unterminated_region_hides_the_rest() {
+    assert_eq!(prompt_width("ab\\[\x1b[1mcd", Shell::Bash), 2);
+    assert_eq!(prompt_width("ab%{cd", Shell::Zsh), 2);
+}
+
+#[test]
+fn test_mark_prompt_escapes() {
+    let raw = "\x1b[32m✔\x1b[0m \x1b]0;title\x07日本 ";
+
+    assert_eq!(
+        mark_prompt_escapes(raw, Shell::Zsh),
+        "%{\x1b[32m%}✔%{\x1b[0m%} %{\x1b]0;title\x07%}日本 "
+    );
+    assert_eq!(
+        mark_prompt_escapes(raw, Shell::Readline),
+        "\x01\x1b[32m\x02✔\x01\x1b[0m\x02 \x01\x1b]0;title\x07\x02日本 "
+    );
+
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Readline] {
+        let marked = mark_prompt_escapes(raw, shell);
+        assert_eq!(prompt_width(&marked, shell), display_width_ansi(raw));
+    }
+}
+
+#[test]
+fn test_mark_prompt_escapes_is_idempotent() {
+    let once = mark_prompt_escapes("\x1b[1m%%\x1b[0m 100%%", Shell::Zsh);
+    assert_eq!(once, "%{\x1b[1m%}%%%{\x1b[0m%} 100%%");
+    assert!(matches!(
+        mark_prompt_escapes(&once, Shell::Zsh),
+        Cow::Borrowed(_)
+    ));
+    assert!(matches!(
+        mark_prompt_escapes("plain $ ", Shell::Bash),
+        Cow::Borrowed(_)
+    ));
+}
+
+/// Decodes a prompt by the shell's own rules, returning its visible and
+/// non-printing parts.
+///
+/// Bash decodes `\\`, `\[`, `\]`, `\e`, and `\a` (other escapes are not used
+/// here); zsh decodes `%%`, `%{`, and `%}`. Panics if a region is left open,
+/// or if zsh would expand any other `%` escape.
+fn decode(ps: &str, shell: Shell) -> (String, String) {
+    let (mut visible, mut hidden) = (String::new(), String::new());
+    let mut in_region = false;
+    let mut chars = ps.chars();
+
+    while let Some(c) = chars.next() {
+        let decoded = match (shell, c) {
+            (Shell::Bash, '\\') => match chars.next() {
+                Some('[') => {
+                    in_region = true;
+                    continue;
+                }
+                Some(']') => {
+                    in_region = false;
+                    continue;
+                }
+                Some('e') => '\x1b',
+                Some('a') => '\x07',
+                Some('\\') => '\\',
+                other => panic!("unexpected bash escape \\{other:?} in {ps:?}"),
+            },
+            (Shell::Zsh, '%') => match chars.next() {
+                Some('{') => {
+                    in_region = true;
+                    continue;
+                }
+                Some('}') => {
+                    in_region = false;
+                    continue;
+                }
+                Some('%') => '%',
+                other => panic!("zsh would expand %{other:?} in {ps:?}"),
+            },
+            _ => c,
+        };
+
+        if in_region {
+            hidden.push(decoded);
+        } else {
+            visible.push(decoded);
+        }
+    }
+
+    assert!(!in_region, "unterminated region in {ps:?}");
+    (visible, hidden)
+}
+
+/// Prompts whose escapes contain `\` and `%`; the visible text has neither.
+const ESCAPED: &[&str] = &[
+    "\x1b]8;;u\x1b\\link\x1b]8;;\x1b\\ $ ",
+    "\x1b]8;;http://x/a%20b\x1b\\日本\x1b]8;;\x1b\\ ❯ ",
+    "\x1b]0;C:\\dir\x07\x1b[1m👍🏽\x1b[0m ",
+];
+
+#[test]
+fn test_marked_escapes_survive_shell_decoding() {
+    for raw in ESCAPED {
+        for shell in [Shell::Bash, Shell::Zsh] {
+            let marked = mark_prompt_escapes(raw, shell);
+            let (visible, hidden) = decode(&marked, shell);
+
+            assert_eq!(visible, strip_ansi(raw), "{shell:?}: {marked:?}");
+            assert_eq!(strip_ansi(&hidden), "", "{shell:?}: {marked:?}");
+            assert_eq!(prompt_width(&marked, shell), display_width_ansi(raw));
+        }
+    }
+}
+
+#[test]
+fn test_marked_escapes_in_real_bash() {
+    for raw in ESCAPED {
+        let marked = mark_prompt_escapes(raw, Shell::Bash);
+
+        // `${p@P}` expands `p` as bash expands PS1, turning the markers into
+        // readline's `\001` and `\002`.
+        let Ok(output) = std::process::Command::new("bash")
+            .args(["-c", "p=$1; printf %s \"${p@P}\"", "_", &marked])
+            .output()
+        else {
+            return; // bash is not installed
+        };
+        let expanded = String::from_utf8(output.stdout).unwrap();
+
+        assert_eq!(
+            prompt_width(&expanded, Shell::Readline),
+            display_width_ansi(raw),
+            "{expanded:?}"
+        );
+        assert_eq!(expanded.replace(['\x01', '\x02'], ""), *raw);
+    }
+}
+
+#[test]
+fn test_doubled_quote_does_not_close_region() {
+    // `\\]` is a literal backslash then `]`, so the region stays open.
+    assert_eq!(prompt_width("\\[\x1b]8;;u\x1b\\\\]link", Shell::Bash), 0);
+    assert_eq!(prompt_width("\\[\x1b]8;;u\x1b\\\\\\]link", Shell::Bash), 4);
+    assert_eq!(prompt_width("%{a%%}b%}c", Shell::Zsh), 1);
+}
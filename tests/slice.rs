@@ -0,0 +1,64 @@
+//! Unit tests for [`slice_by_columns`].
+//!
+//! These tests verify column-range extraction for horizontal scrolling,
+//! including wide graphemes straddling either edge of the range.
+
+use runefix_core::{ColumnSlice, display_width, slice_by_columns};
+
+#[test]
+fn test_slice_ascii() {
+    let slice = slice_by_columns("Hello, world", 7, 12);
+    assert_eq!(slice.text, "world");
+    assert_eq!((slice.width, slice.left_pad, slice.right_pad), (5, 0, 0));
+}
+
+#[test]
+fn test_slice_wide_edges() {
+    let slice = slice_by_columns("你a1👇", 1, 5);
+    assert_eq!(slice.text, "a1");
+    assert_eq!((slice.left_pad, slice.right_pad), (1, 1));
+    assert_eq!(slice.fill_with(">"), ">a1>");
+}
+
+#[test]
+fn test_fill_with_measures_filler() {
+    let slice = slice_by_columns("你a1👇", 1, 5);
+    // `…` is 2 columns wide and does not fit a 1-column edge.
+    assert_eq!(slice.fill_with("…"), " a1 ");
+
+    let slice = ColumnSlice {
+        text: "x",
+        width: 1,
+        left_pad: 3,
+        right_pad: 1,
+    };
+    assert_eq!(slice.fill_with("-="), "-= x ");
+    assert_eq!(display_width(&slice.fill_with("…")), 5);
+}
+
+#[test]
+fn test_slice_aligned_edges() {
+    let slice = slice_by_columns("你好世界", 2, 6);
+    assert_eq!(slice.text, "好世");
+    assert_eq!((slice.left_pad, slice.right_pad), (0, 0));
+}
+
+#[test]
+fn test_slice_past_end() {
+    let slice = slice_by_columns("你a1👇", 3, 20);
+    assert_eq!(slice.text, "1👇");
+    assert_eq!(slice.width, 3);
+}
+
+#[test]
+fn test_slice_inside_single_wide_grapheme() {
+    let slice = slice_by_columns("你好", 1, 2);
+    assert_eq!(slice.text, "");
+    assert_eq!(slice.fill_with(" "), " ");
+}
+
+#[test]
+fn test_slice_empty_range() {
+    assert_eq!(slice_by_columns("abc", 2, 2).text, "");
+    assert_eq!(slice_by_columns("abc", 3, 1).text, "");
+}
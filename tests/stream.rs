@@ -0,0 +1,85 @@
+//! Unit tests for [`StreamMeasurer`].
+//!
+//! These tests feed inputs split at every byte position and verify that the
+//! emitted graphemes and widths match measuring the concatenated input.
+
+use runefix_core::*;
+
+const SAMPLES: &[&str] = &[
+    "",
+    "你a1👇",
+    "Hello 👋 世界！\nsecond",
+    "cafe\u{301} x\r\ny",
+    "👩\u{200d}💻 한국어 カタカナ",
+    "🇯🇵🇰🇷",
+];
+
+fn feed_all(m: &mut StreamMeasurer, chunks: &[&[u8]]) -> Vec<(String, usize)> {
+    let mut events = Vec::new();
+    for chunk in chunks {
+        events.extend(m.feed(chunk));
+    }
+    events.extend(m.flush());
+    events
+}
+
+fn expected(s: &str) -> Vec<(String, usize)> {
+    grapheme_widths(s)
+        .into_iter()
+        .map(|(g, w)| (g.to_string(), w))
+        .collect()
+}
+
+#[test]
+fn test_every_byte_split() {
+    for s in SAMPLES {
+        let bytes = s.as_bytes();
+        for i in 0..=bytes.len() {
+            let mut m = StreamMeasurer::new();
+            let events = feed_all(&mut m, &[&bytes[..i], &bytes[i..]]);
+
+            assert_eq!(events, expected(s), "{s:?} split at {i}");
+            assert_eq!(m.width(), display_width(s), "{s:?} split at {i}");
+            assert!(m.is_idle());
+        }
+    }
+}
+
+#[test]
+fn test_one_byte_at_a_time() {
+    for s in SAMPLES {
+        let mut m = StreamMeasurer::new();
+        let chunks: Vec<&[u8]> = s.as_bytes().chunks(1).collect();
+
+        assert_eq!(feed_all(&mut m, &chunks), expected(s), "{s:?}");
+    }
+}
+
+#[test]
+fn test_holds_back_extendable_grapheme() {
+    let mut m = StreamMeasurer::new();
+    assert_eq!(m.feed(b"ab"), vec![("a".to_string(), 1)]);
+    assert_eq!(
+        m.feed("\u{301}c".as_bytes()),
+        vec![("b\u{301}".to_string(), 1)]
+    );
+    assert_eq!(m.column(), 2);
+    assert!(!m.is_idle());
+}
+
+#[test]
+fn test_column_resets_on_newline() {
+    let mut m = StreamMeasurer::new();
+    m.feed("世界\nab".as_bytes());
+    m.flush();
+    assert_eq!(m.column(), 2);
+    assert_eq!(m.width(), 6);
+}
+
+#[test]
+fn test_invalid_utf8_is_replaced() {
+    let mut m = StreamMeasurer::new();
+    let events = feed_all(&mut m, &[b"a\xFFb", b"\xE4\xBD"]);
+    let graphemes: Vec<_> = events.iter().map(|(g, _)| g.as_str()).collect();
+    assert_eq!(graphemes, vec!["a", "\u{FFFD}", "b", "\u{FFFD}"]);
+}
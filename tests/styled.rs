@@ -0,0 +1,126 @@
+//! Unit tests for [`truncate_ansi`] and [`wrap_ansi`].
+//!
+//! These tests verify that escape sequences are never cut, that SGR styles
+//! are closed and reopened at line breaks and truncation points, and that
+//! OSC 8 hyperlinks stay balanced.
+
+use std::borrow::Cow;
+
+use runefix_core::*;
+
+const LINK_OPEN: &str = "\x1b]8;;https://example.com\x1b\\";
+const LINK_CLOSE: &str = "\x1b]8;;\x1b\\";
+
+#[test]
+fn test_truncate_closes_style() {
+    assert_eq!(
+        truncate_ansi("\x1b[31mhello\x1b[0m", 3),
+        "\x1b[31mhel\x1b[0m"
+    );
+    assert_eq!(truncate_ansi("ab\x1b[1mcd", 3), "ab\x1b[1mc\x1b[0m");
+}
+
+#[test]
+#[cfg(feature = "emoji-zwj")]
+fn test_truncate_never_cuts_escape_or_wide_grapheme() {
+    assert_eq!(
+        truncate_ansi("a\x1b[38;2;1;2;3m你", 2),
+        "a\x1b[38;2;1;2;3m\x1b[0m"
+    );
+    assert_eq!(truncate_ansi("\x1b[4m👩\u{200d}💻x", 1), "\x1b[4m\x1b[0m");
+}
+
+#[test]
+fn test_truncate_without_open_style_adds_nothing() {
+    assert_eq!(
+        truncate_ansi("\x1b[31mred\x1b[0m tail", 4),
+        "\x1b[31mred\x1b[0m "
+    );
+    assert_eq!(truncate_ansi("plain text", 5), "plain");
+}
+
+#[test]
+fn test_truncate_fitting_input_is_borrowed() {
+    let s = "\x1b[31m你好\x1b[0m";
+    assert!(matches!(truncate_ansi(s, 4), Cow::Borrowed(_)));
+}
+
+#[test]
+fn test_truncate_closes_hyperlink() {
+    let s = format!("{LINK_OPEN}\x1b[34mlink text\x1b[0m{LINK_CLOSE}");
+    assert_eq!(
+        truncate_ansi(&s, 4),
+        format!("{LINK_OPEN}\x1b[34mlink\x1b[0m{LINK_CLOSE}")
+    );
+}
+
+#[test]
+fn test_wrap_reopens_style_on_each_line() {
+    let lines = wrap_ansi("\x1b[1;32mabcdef\x1b[0mgh", 3);
+    assert_eq!(
+        lines,
+        vec!["\x1b[1;32mabc\x1b[0m", "\x1b[1;32mdef\x1b[0m", "gh",]
+    );
+}
+
+#[test]
+fn test_wrap_rebuilds_compact_state() {
+    // Many sequences collapse into one reopened SGR sequence.
+    let lines = wrap_ansi(
+        "\x1b[31m\x1b[1m\x1b[32m\x1b[22m\x1b[4:3m\x1b[48;5;200mabcd",
+        2,
+    );
+    assert_eq!(lines[1], "\x1b[4:3;32;48;5;200mcd\x1b[0m");
+
+    let lines = wrap_ansi("\x1b[38:2::10:20:30mabcd", 2);
+    assert_eq!(lines[1], "\x1b[38;2;10;20;30mcd\x1b[0m");
+}
+
+#[test]
+fn test_wrap_keeps_hyperlink_balanced() {
+    let s = format!("{LINK_OPEN}abcd{LINK_CLOSE}e");
+    assert_eq!(
+        wrap_ansi(&s, 2),
+        vec![
+            format!("{LINK_OPEN}ab{LINK_CLOSE}"),
+            format!("{LINK_OPEN}cd{LINK_CLOSE}"),
+            "e".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_wrap_hard_lines() {
+    let lines = wrap_ansi("\x1b[33m你好世界\n\nok\x1b[0m", 4);
+    assert_eq!(
+        lines,
+        vec![
+            "\x1b[33m你好\x1b[0m",
+            "\x1b[33m世界\x1b[0m",
+            "\x1b[33m\x1b[0m",
+            "\x1b[33mok\x1b[0m",
+        ]
+    );
+}
+
+#[test]
+fn test_wrap_plain_text_matches_wrap_lines() {
+    for s in [
+        "",
+        "Hello 👋 世界！",
+        "a\n\nbc\n",
+        "a\r\n\r\nbc\r\n",
+        "你好世界abc",
+    ] {
+        assert_eq!(wrap_ansi(s, 3), wrap_lines(s, 3), "{s:?}");
+    }
+}
+
+#[test]
+fn test_wrapped_lines_measure_within_width() {
+    let s = "\x1b[31mred \x1b[1mbold 红色 \x1b]8;;x\x07链接\x1b]8;;\x07\x1b[0m end";
+    for line in wrap_ansi(s, 5) {
+        assert!(display_width_ansi(&line) <= 5, "{line:?}");
+    }
+    assert_eq!(strip_ansi(&wrap_ansi(s, 5).concat()), strip_ansi(s));
+}
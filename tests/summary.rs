@@ -0,0 +1,126 @@
+//! Unit tests for [`WidthSummary`].
+//!
+//! These tests verify that combining per-chunk summaries yields the same
+//! result as measuring the concatenated text, for every split position and
+//! grouping, including clusters that straddle chunk boundaries.
+
+use runefix_core::*;
+
+const SAMPLES: &[&str] = &[
+    "",
+    "你a1👇",
+    "Hello 👋 世界！\nsecond line",
+    "e\u{301}x\u{0}y\r\nz",
+    "👩\u{200d}💻 한국어 カタカナ",
+    "🇯🇵🇰🇷🇺🇸",
+    "क्षि नमस्ते",
+    "a\n\n👨\u{200d}👩\u{200d}👧\u{200d}👦\n",
+];
+
+fn expected(s: &str) -> (usize, usize, usize) {
+    let last_line = s.rsplit('\n').next().unwrap_or_default();
+
+    (
+        display_width(s),
+        s.matches('\n').count() + 1,
+        display_width(last_line),
+    )
+}
+
+fn actual(summary: &WidthSummary) -> (usize, usize, usize) {
+    (
+        summary.width(),
+        summary.line_count(),
+        summary.last_line_width(),
+    )
+}
+
+fn char_boundaries(s: &str) -> Vec<usize> {
+    s.char_indices().map(|(i, _)| i).chain([s.len()]).collect()
+}
+
+#[test]
+fn test_single_chunk() {
+    for s in SAMPLES {
+        assert_eq!(actual(&WidthSummary::new(s)), expected(s), "{s:?}");
+    }
+}
+
+#[test]
+fn test_two_chunks_every_split() {
+    for s in SAMPLES {
+        for i in char_boundaries(s) {
+            let (a, b) = s.split_at(i);
+            let combined = WidthSummary::new(a).combine(&WidthSummary::new(b));
+
+            assert_eq!(actual(&combined), expected(s), "{a:?} + {b:?}");
+            assert_eq!(combined, WidthSummary::new(s), "{a:?} + {b:?}");
+        }
+    }
+}
+
+#[test]
+fn test_associativity() {
+    for s in SAMPLES {
+        let bounds = char_boundaries(s);
+
+        for (x, &i) in bounds.iter().enumerate() {
+            for &j in &bounds[x..] {
+                let a = WidthSummary::new(&s[..i]);
+                let b = WidthSummary::new(&s[i..j]);
+                let c = WidthSummary::new(&s[j..]);
+
+                let left = a.combine(&b).combine(&c);
+                let right = a.combine(&b.combine(&c));
+
+                assert_eq!(left, right, "{s:?} at {i}, {j}");
+                assert_eq!(actual(&left), expected(s), "{s:?} at {i}, {j}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_one_char_per_chunk() {
+    for s in SAMPLES {
+        let total = s
+            .chars()
+            .map(|c| WidthSummary::new(c.encode_utf8(&mut [0; 4])))
+            .fold(WidthSummary::default(), |acc, x| acc.combine(&x));
+
+        assert_eq!(actual(&total), expected(s), "{s:?}");
+    }
+}
+
+#[test]
+fn test_identity() {
+    let s = WidthSummary::new("你a\nb");
+    assert!(WidthSummary::default().is_empty());
+    assert_eq!(WidthSummary::default().combine(&s), s);
+    assert_eq!(s.combine(&WidthSummary::default()), s);
+}
+
+#[test]
+#[cfg(feature = "emoji-zwj")]
+fn test_zwj_straddling_chunks() {
+    let a = WidthSummary::new("ab👩\u{200d}");
+    let b = WidthSummary::new("💻cd");
+
+    // Measured alone, the halves over-count the split sequence.
+    assert!(a.width() + b.width() > 6);
+    assert_eq!(a.combine(&b).width(), 6);
+}
+
+#[test]
+fn test_combining_mark_straddling_chunks() {
+    let a = WidthSummary::new("cafe");
+    let b = WidthSummary::new("\u{301} au lait");
+    assert_eq!(a.combine(&b).width(), display_width("cafe\u{301} au lait"));
+}
+
+#[test]
+fn test_pending_is_bounded() {
+    // Only the trailing cluster stays unresolved, not the whole chunk.
+    let s = WidthSummary::new("Hello, 世界 👋");
+    assert_eq!(s.pending(), "👋");
+}
@@ -0,0 +1,62 @@
+//! Unit tests for [`truncate_with`] ellipsis truncation.
+//!
+//! These tests verify ellipsis placement (end / start / middle),
+//! ellipsis width accounting, and padding reports when a wide
+//! grapheme cannot fill the remaining column.
+
+use runefix_core::{TruncateOptions, truncate_with};
+
+#[test]
+fn test_no_truncation_borrows() {
+    let t = truncate_with("你a1👇", 8, &TruncateOptions::default());
+    assert_eq!(t.text, "你a1👇");
+    assert!(!t.truncated);
+    assert_eq!((t.width, t.padding), (6, 2));
+}
+
+#[test]
+fn test_truncate_end() {
+    let opts = TruncateOptions::end().with_ellipsis("...");
+    let t = truncate_with("Hello, world", 8, &opts);
+    assert_eq!(t.text, "Hello...");
+    assert_eq!((t.width, t.padding), (8, 0));
+}
+
+#[test]
+fn test_truncate_start() {
+    let opts = TruncateOptions::start().with_ellipsis("...");
+    let t = truncate_with("Hello, world", 8, &opts);
+    assert_eq!(t.text, "...world");
+}
+
+#[test]
+fn test_truncate_middle() {
+    let opts = TruncateOptions::middle().with_ellipsis("..");
+    let t = truncate_with("abcdefghij", 7, &opts);
+    assert_eq!(t.text, "abc..ij");
+}
+
+#[test]
+fn test_ellipsis_width_is_measured() {
+    // "…" is a fullwidth punctuation (2 columns) in terminal layout
+    let t = truncate_with("abcdefghij", 5, &TruncateOptions::end());
+    assert_eq!(t.text, "abc…");
+    assert_eq!(t.width, 5);
+}
+
+#[test]
+fn test_wide_grapheme_reports_padding() {
+    let opts = TruncateOptions::end().with_ellipsis(".");
+    let t = truncate_with("你好世界", 4, &opts);
+    assert_eq!(t.text, "你.");
+    assert!(t.truncated);
+    assert_eq!((t.width, t.padding), (3, 1));
+}
+
+#[test]
+fn test_ellipsis_wider_than_max_width() {
+    let opts = TruncateOptions::end().with_ellipsis("...");
+    let t = truncate_with("Hello", 2, &opts);
+    assert_eq!(t.text, "..");
+    assert_eq!(t.width, 2);
+}
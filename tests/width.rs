@@ -43,11 +43,13 @@ fn test_emoji_base() {
 }
 
 #[test]
+#[cfg(feature = "emoji-zwj")]
 fn test_emoji_family() {
     assert_eq!("👨‍👩‍👧‍👦".rune_width(), 2); // Emoji ZWJ family sequence
 }
 
 #[test]
+#[cfg(feature = "emoji-zwj")]
 fn test_emoji_zwj() {
     let emoji = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
     assert_eq!(emoji.rune_width(), 2); // Same as 👨‍👩‍👧‍👦 (decomposed)
@@ -67,3 +69,45 @@ fn test_cjk_ext_b() {
 fn test_unknown_char() {
     assert_eq!("\u{10FFFF}".rune_width(), 1); // Max valid Unicode codepoint (unassigned)
 }
+
+#[test]
+fn test_fast_path_matches_full_path() {
+    use runefix_core::{display_width, display_widths};
+
+    let samples = [
+        "",
+        "plain ascii text that is longer than eight bytes",
+        "Ünïcödé Ελληνικά Кириллица Tiếng Việt",
+        "e\u{301}e\u{301} combining",
+        "line one\r\nline two\n\tindented\x7f",
+        "mixed ascii 你好 and emoji 👩‍💻 then ascii again",
+        "Ωmega then 🇯🇵 flags and Ж",
+        "ж\u{489} cyrillic mark, a\u{200d}b zwj",
+    ];
+
+    for s in samples {
+        assert_eq!(
+            display_width(s),
+            display_widths(s).iter().sum::<usize>(),
+            "{s:?}"
+        );
+    }
+}
+
+#[test]
+fn test_fast_path_every_narrow_char() {
+    use runefix_core::{display_width, display_widths};
+
+    for cp in (0x20..0x600).chain(0x1E00..0x2000) {
+        let Some(c) = char::from_u32(cp) else {
+            continue;
+        };
+        for s in [format!("{c}"), format!("a{c}b"), format!("{c}\u{301}x{c}")] {
+            assert_eq!(
+                display_width(&s),
+                display_widths(&s).iter().sum::<usize>(),
+                "{s:?}"
+            );
+        }
+    }
+}
@@ -89,3 +89,274 @@ fn test_deref_behavior() {
     let view = binding.apply("abc");
     assert!(view.starts_with("a")); // thanks to Deref<Target=str>
 }
+
+#[test]
+fn test_truncate_with_behavior() {
+    use runefix_core::TruncateOptions;
+
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("👋👋👋👋👋");
+    let t = view.truncate_with(4, &TruncateOptions::end().with_ellipsis("."));
+    assert_eq!(t.text, "👋👋👋.");
+    assert_eq!(t.padding, 0);
+}
+
+#[test]
+fn test_truncate_path_behavior() {
+    let policy = WidthPolicy::compact(); // cjk = 1
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("/home/user/プロジェクト/main.rs");
+    assert_eq!(view.truncate_path(22), "/home/…/プロジェクト/main.rs");
+}
+
+#[test]
+fn test_pad_and_fit_behavior() {
+    use runefix_core::Align;
+
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("👋世");
+    assert_eq!(view.pad_left(5, " "), "  👋世");
+    assert_eq!(view.fit(2, Align::Left), "👋 ");
+}
+
+#[test]
+fn test_columns_behavior() {
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("👋世");
+    assert_eq!(format!("{:>5}|", view.columns()), "  👋世|");
+}
+
+#[test]
+fn test_slice_by_columns_behavior() {
+    let policy = WidthPolicy::compact(); // cjk = 1
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("你好世界");
+    assert_eq!(view.slice_by_columns(1, 3).text, "好世");
+}
+
+#[test]
+fn test_fill_with_policy_measures_filler() {
+    let slice = runefix_core::slice_by_columns("你a1👇", 1, 5);
+    assert_eq!(slice.fill_with("世"), " a1 ");
+
+    let policy = WidthPolicy::compact(); // cjk = 1
+    assert_eq!(slice.fill_with_policy("世", Some(&policy)), "世a1世");
+}
+
+#[test]
+fn test_column_mapping_behavior() {
+    use runefix_core::Bias;
+
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("👋世a");
+    assert_eq!(view.column_of_byte(7), 3);
+    assert_eq!(view.byte_at_column(2, Bias::Right), 7);
+    assert_eq!(view.columns_of_byte_range(0..4), 0..1);
+}
+
+#[test]
+fn test_lazy_iter_behavior() {
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("👋世a");
+    let columns: Vec<usize> = view.grapheme_widths_iter().map(|(.., c)| c).collect();
+    assert_eq!(columns, vec![0, 1, 3]);
+    assert_eq!(view.wrap_iter(3).collect::<Vec<_>>(), vec!["👋世", "a"]);
+}
+
+#[test]
+fn test_fast_path_uses_policy_fallback() {
+    let policy = WidthPolicy {
+        fallback: 2,
+        ..WidthPolicy::terminal()
+    };
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("abc Ωж é");
+    assert_eq!(
+        view.display_width(),
+        view.display_widths().iter().sum::<usize>()
+    );
+    assert_eq!(view.display_width(), 11);
+}
+
+#[test]
+fn test_measure_behavior() {
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("👋世a");
+    let m = view.measure();
+    assert_eq!(m.width(), 4);
+    assert_eq!(m.truncate(2), "👋");
+}
+
+#[test]
+fn test_summarize_behavior() {
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let binding = WithPolicy::new(&policy);
+    let a = binding.apply("👩\u{200d}").summarize();
+    let b = binding.apply("💻世\na").summarize();
+    let total = a.combine(&b);
+    assert_eq!(total.width(), 4);
+    assert_eq!(total.line_count(), 2);
+    assert_eq!(total.last_line_width(), 1);
+}
+
+#[test]
+fn test_stream_measurer_with_policy() {
+    use runefix_core::StreamMeasurer;
+
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let mut m = StreamMeasurer::with_policy(Some(&policy));
+    let bytes = "👋世".as_bytes();
+    let mut events = m.feed(&bytes[..2]);
+    events.extend(m.feed(&bytes[2..]));
+    events.extend(m.flush());
+    assert_eq!(events, vec![("👋".to_string(), 1), ("世".to_string(), 2)]);
+    assert_eq!(m.column(), 3);
+}
+
+#[test]
+fn test_wrap_writer_with_policy() {
+    use runefix_core::WrapWriter;
+    use std::io::Write;
+
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    let mut w = WrapWriter::with_policy(Vec::new(), 3, Some(&policy));
+    w.write_all("👋👋世a".as_bytes()).unwrap();
+    w.flush().unwrap();
+    assert_eq!(w.into_inner().unwrap(), "👋👋\n世a".as_bytes());
+}
+
+#[test]
+fn test_width_cache_keyed_by_policy() {
+    use runefix_core::WidthCache;
+
+    let cache = WidthCache::new(8);
+    let markdown = WidthPolicy::markdown(); // emoji = 1
+    assert_eq!(cache.display_width("👋世"), 4);
+    assert_eq!(cache.display_width_with_policy("👋世", Some(&markdown)), 3);
+    assert_eq!(cache.display_width_with_policy("👋世", None), 4);
+    assert_eq!(cache.stats().hits, 1);
+    assert_eq!(cache.stats().len, 2);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_with_policy() {
+    use runefix_core::{
+        display_widths_par_with_policy, max_column_widths_par_with_policy,
+        wrap_lines_par_with_policy, wrap_lines_with_policy,
+    };
+
+    let policy = WidthPolicy::markdown(); // emoji = 1
+    assert_eq!(
+        display_widths_par_with_policy(&["👋", "世"], Some(&policy)),
+        vec![1, 2]
+    );
+    assert_eq!(
+        max_column_widths_par_with_policy(&[["👋👋", "a"], ["世", "bb"]], Some(&policy)),
+        vec![2, 2]
+    );
+
+    let doc = "👋世界\n".repeat(10_000);
+    assert_eq!(
+        wrap_lines_par_with_policy(&doc, 3, Some(&policy)),
+        wrap_lines_with_policy(&doc, 3, Some(&policy))
+    );
+}
+
+#[test]
+fn test_display_width_ansi() {
+    let policy = WidthPolicy::markdown();
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("\x1b[32m👋\x1b[0m ok");
+    assert_eq!(view.display_width_ansi(), 4);
+}
+
+#[test]
+fn test_truncate_and_wrap_ansi() {
+    let policy = WidthPolicy::markdown();
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("\x1b[31m👋👋👋\x1b[0m");
+    assert_eq!(view.truncate_ansi(2), "\x1b[31m👋👋\x1b[0m");
+    assert_eq!(view.wrap_ansi(2).len(), 2);
+}
+
+#[test]
+fn test_prompt_width() {
+    use runefix_core::Shell;
+
+    let policy = WidthPolicy::compact();
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("\\[\x1b[1m\\]项目 ❯ ");
+    assert_eq!(view.prompt_width(Shell::Bash), 5);
+}
+
+#[test]
+fn test_cursor_sim_with_policy() {
+    use runefix_core::CursorSim;
+
+    let policy = WidthPolicy::compact();
+    let mut sim = CursorSim::with_policy(4, Some(&policy));
+    sim.print("中文字符");
+    assert_eq!(sim.position(), (0, 3));
+    assert!(sim.is_wrap_pending());
+}
+
+#[test]
+fn test_cell_grid_with_policy() {
+    use runefix_core::{Cell, CellGrid};
+
+    let policy = WidthPolicy::compact();
+    let mut grid = CellGrid::with_policy(1, 4, Some(&policy));
+    grid.print("中😀");
+
+    assert_eq!(grid.lines(), ["中😀  "]);
+    assert_eq!(
+        grid.cell(0, 1),
+        Some(&Cell::Lead {
+            grapheme: "😀".into(),
+            width: 1
+        })
+    );
+}
+
+#[test]
+fn test_render_html_and_svg_with_policy() {
+    let policy = WidthPolicy::compact();
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("中😀");
+
+    assert!(
+        view.render_html()
+            .contains("<div><span class=\"w1\">中</span><span class=\"w1\">😀</span></div>")
+    );
+    assert!(view.render_svg().contains("width=\"20\" height=\"20\""));
+}
+
+#[test]
+fn test_encode_text_sizing_with_policy() {
+    use runefix_core::{TextSizingMode, display_width_ansi_with_policy};
+
+    let policy = WidthPolicy::compact();
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("中😀");
+    let encoded = view.encode_text_sizing(TextSizingMode::All);
+
+    assert_eq!(encoded, "\x1b]66;w=1;中\x1b\\\x1b]66;w=1;😀\x1b\\");
+    assert_eq!(display_width_ansi_with_policy(&encoded, Some(&policy)), 2);
+}
+
+#[test]
+fn test_render_aligned_with_policy() {
+    let policy = WidthPolicy::compact();
+    let binding = WithPolicy::new(&policy);
+    let view = binding.apply("中😀a");
+
+    // Emoji still get a move, to the column the policy expects.
+    assert_eq!(view.render_aligned(0), "中😀\x1b[3Ga");
+}
@@ -0,0 +1,232 @@
+//! Unit tests for [`WrapWriter`] and [`TruncateWriter`].
+//!
+//! These tests verify that output matches the string-based layout functions
+//! regardless of how the input bytes are split across `write` calls.
+
+use std::io::Write;
+
+use runefix_core::*;
+
+const SAMPLES: &[&str] = &[
+    "",
+    "你a1👇",
+    "Hello 👋 世界！\nsecond line",
+    "cafe\u{301} au lait",
+    "👩\u{200d}💻 한국어 カタカナ\n\nend",
+];
+
+fn wrap_expected(s: &str, max_width: usize) -> String {
+    s.split('\n')
+        .map(|line| split_by_width(line, max_width).join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn truncate_expected(s: &str, max_width: usize) -> String {
+    s.split('\n')
+        .map(|line| truncate_by_width(line, max_width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn write_split<W: Write>(w: &mut W, s: &str, at: usize) {
+    let bytes = s.as_bytes();
+    w.write_all(&bytes[..at]).unwrap();
+    w.write_all(&bytes[at..]).unwrap();
+    w.flush().unwrap();
+}
+
+#[test]
+fn test_wrap_every_split() {
+    for s in SAMPLES {
+        for max_width in 1..=6 {
+            for at in 0..=s.len() {
+                let mut w = WrapWriter::new(Vec::new(), max_width);
+                write_split(&mut w, s, at);
+
+                let out = String::from_utf8(w.into_inner().unwrap()).unwrap();
+                assert_eq!(out, wrap_expected(s, max_width), "{s:?} {max_width} {at}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_truncate_every_split() {
+    for s in SAMPLES {
+        for max_width in 0..=6 {
+            for at in 0..=s.len() {
+                let mut w = TruncateWriter::new(Vec::new(), max_width);
+                write_split(&mut w, s, at);
+
+                let out = String::from_utf8(w.into_inner().unwrap()).unwrap();
+                assert_eq!(
+                    out,
+                    truncate_expected(s, max_width),
+                    "{s:?} {max_width} {at}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_flush_mid_cluster_keeps_bytes() {
+    let mut w = WrapWriter::new(Vec::new(), 10);
+    w.write_all(b"cafe").unwrap();
+    w.flush().unwrap();
+    assert_eq!(w.get_ref(), b"cafe");
+    assert_eq!(w.column(), 4);
+
+    // The combining mark extends the already flushed `e`.
+    w.write_all("\u{301}!".as_bytes()).unwrap();
+    w.flush().unwrap();
+    assert_eq!(w.get_ref().as_slice(), "cafe\u{301}!".as_bytes());
+    assert_eq!(w.column(), 5);
+}
+
+#[test]
+fn test_unflushed_input_is_buffered() {
+    let mut w = TruncateWriter::new(Vec::new(), 10);
+    w.write_all(b"ab").unwrap();
+    assert_eq!(w.get_ref(), b"a");
+}
+
+#[test]
+fn test_wide_grapheme_wraps_alone() {
+    let mut w = WrapWriter::new(Vec::new(), 1);
+    w.write_all("a你b".as_bytes()).unwrap();
+    w.flush().unwrap();
+    assert_eq!(w.into_inner().unwrap(), "a\n你\nb".as_bytes());
+}
+
+#[test]
+fn test_into_inner_writes_last_grapheme() {
+    let mut w = WrapWriter::new(Vec::new(), 10);
+    w.write_all(b"ab").unwrap();
+    assert_eq!(w.into_inner().unwrap(), b"ab");
+
+    let mut w = TruncateWriter::new(Vec::new(), 1);
+    w.write_all(b"ab\nc").unwrap();
+    assert_eq!(w.into_inner().unwrap(), b"a\nc");
+}
+
+#[test]
+fn test_drop_writes_last_grapheme() {
+    let mut out = Vec::new();
+    {
+        let mut w = WrapWriter::new(&mut out, 10);
+        w.write_all("ab👍".as_bytes()).unwrap();
+    }
+    assert_eq!(out, "ab👍".as_bytes());
+
+    {
+        let mut w = TruncateWriter::new(&mut out, 1);
+        w.write_all(b"cd").unwrap();
+    }
+    assert_eq!(out, "ab👍c".as_bytes());
+}
+
+/// Accepts a limited number of bytes, then fails.
+#[derive(Debug)]
+struct Limited(Vec<u8>, usize);
+
+impl Write for Limited {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.0.len() + buf.len() > self.1 {
+            return Err(std::io::ErrorKind::WriteZero.into());
+        }
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_into_inner_error_returns_writer() {
+    let mut w = WrapWriter::new(Limited(Vec::new(), 1), 10);
+    w.write_all(b"ab").unwrap();
+
+    let err = w.into_inner().unwrap_err();
+    assert_eq!(err.error().kind(), std::io::ErrorKind::WriteZero);
+
+    let mut w = err.into_inner();
+    w.get_mut().1 = 2;
+    assert_eq!(w.into_inner().unwrap().0, b"ab");
+}
+
+#[test]
+fn test_extended_grapheme_stays_within_max_width() {
+    // A combining mark extends the last grapheme written by `flush`.
+    let mut w = WrapWriter::new(Vec::new(), 2);
+    w.write_all(b"ab").unwrap();
+    w.flush().unwrap();
+    w.write_all("\u{301}".as_bytes()).unwrap();
+    w.flush().unwrap();
+    assert_eq!(w.column(), 2);
+
+    // VS16 makes `☺` two columns wide, past the end of the line.
+    for max_width in [2, 3] {
+        let mut w = TruncateWriter::new(Vec::new(), max_width);
+        w.write_all("a☺".as_bytes()).unwrap();
+        w.flush().unwrap();
+        w.write_all("\u{fe0f}x".as_bytes()).unwrap();
+        w.flush().unwrap();
+        assert_eq!(w.column(), 3.min(max_width));
+        assert_eq!(w.get_ref().as_slice(), "a☺\u{fe0f}".as_bytes());
+
+        let mut w = WrapWriter::new(Vec::new(), max_width);
+        w.write_all("a☺".as_bytes()).unwrap();
+        w.flush().unwrap();
+        w.write_all("\u{fe0f}x".as_bytes()).unwrap();
+        w.flush().unwrap();
+        assert_eq!(w.get_ref().as_slice(), "a☺\u{fe0f}\nx".as_bytes());
+        assert_eq!(w.column(), 1);
+    }
+}
+
+#[test]
+fn test_incomplete_utf8_at_end_becomes_replacement() {
+    let bytes = "a你".as_bytes();
+
+    // `flush` keeps the partial sequence, which a later write may complete.
+    let mut w = WrapWriter::new(Vec::new(), 10);
+    w.write_all(&bytes[..3]).unwrap();
+    w.flush().unwrap();
+    assert_eq!(w.get_ref().as_slice(), b"a");
+    w.write_all(&bytes[3..]).unwrap();
+    assert_eq!(w.into_inner().unwrap(), bytes);
+
+    let mut w = TruncateWriter::new(Vec::new(), 10);
+    w.write_all(&bytes[..3]).unwrap();
+    w.flush().unwrap();
+    assert_eq!(w.into_inner().unwrap(), "a\u{fffd}".as_bytes());
+
+    let mut out = Vec::new();
+    {
+        let mut w = WrapWriter::new(&mut out, 10);
+        w.write_all(&bytes[..2]).unwrap();
+    }
+    assert_eq!(out, "a\u{fffd}".as_bytes());
+}
+
+#[test]
+fn test_write_error_keeps_accepted_graphemes() {
+    let mut w = WrapWriter::new(Limited(Vec::new(), 1), 10);
+
+    // `b` cannot be written, but the measurer has consumed the whole buffer.
+    assert_eq!(w.write(b"abc").unwrap(), 3);
+    assert_eq!(w.get_ref().0, b"a");
+
+    // The queued `b` goes first, so `d` is not accepted while it fails.
+    let err = w.write(b"d").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    assert!(w.flush().is_err());
+
+    w.get_mut().1 = 10;
+    assert_eq!(w.write(b"d").unwrap(), 1);
+    assert_eq!(w.into_inner().unwrap().0, b"abcd");
+}